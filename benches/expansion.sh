@@ -0,0 +1,95 @@
+#!/usr/bin/env bash
+# Measures how much code the derive expands to, for a fixed fixture crate.
+# Run with `benches/expansion.sh [REV...]`: with no revision it measures the working tree, otherwise
+# each git revision is checked out into a temporary worktree and measured in turn.
+# The fixture holds a 5 variant kebab-cased enum, a 20 variant enum with parse(prefix, allow_numeric)
+# and a 3 variant enum with fields. Expansion is printed with -Zunpretty=expanded (through
+# RUSTC_BOOTSTRAP on stable), formatted with rustfmt, and counted in lines and bytes.
+# Set CARGO_NET_OFFLINE=true to build without network access.
+set -euo pipefail
+
+repo=$(git -C "$(dirname "$0")" rev-parse --show-toplevel)
+scratch=$(mktemp -d)
+trap 'git -C "$repo" worktree prune; rm -rf "$scratch"' EXIT
+
+fixture() {
+    mkdir -p "$scratch/fixture/src"
+    cat > "$scratch/fixture/Cargo.toml" <<EOF
+[package]
+name = "expansion_fixture"
+version = "0.0.0"
+edition = "2021"
+publish = false
+
+[workspace]
+
+[dependencies]
+to_and_fro = { path = "$1" }
+EOF
+    cat > "$scratch/fixture/src/lib.rs" <<'EOF'
+use to_and_fro::ToAndFro;
+
+#[derive(ToAndFro)]
+#[to_and_fro(casing = "kebab")]
+pub enum Status {
+    Pending,
+    InProgress,
+    Blocked,
+    Done,
+    Cancelled,
+}
+
+#[derive(ToAndFro)]
+#[to_and_fro(casing = "lower", parse(prefix, allow_numeric))]
+pub enum Command {
+    Add,
+    Branch,
+    Checkout,
+    Clone,
+    Commit,
+    Diff,
+    Fetch,
+    Init,
+    Log,
+    Merge,
+    Move,
+    Pull,
+    Push,
+    Rebase,
+    Remove,
+    Reset,
+    Restore,
+    Show,
+    Stash,
+    Tag,
+}
+
+#[derive(ToAndFro)]
+#[to_and_fro(casing = "snake")]
+pub enum Shape {
+    Point,
+    Circle(u32),
+    Rect { width: u32, height: u32 },
+}
+EOF
+}
+
+measure() {
+    fixture "$1"
+    CARGO_TARGET_DIR="$scratch/target" RUSTC_BOOTSTRAP=1 \
+        cargo rustc -q --manifest-path "$scratch/fixture/Cargo.toml" --lib -- -Zunpretty=expanded \
+        | rustfmt --edition 2021 \
+        | wc -lc \
+        | awk '{ printf "%6d lines, %7d bytes\n", $1, $2 }'
+}
+
+if [ "$#" -eq 0 ]; then
+    printf '%-12s %s\n' "worktree" "$(measure "$repo")"
+fi
+
+for rev in "$@"; do
+    tree="$scratch/$(git -C "$repo" rev-parse --short "$rev")"
+    git -C "$repo" worktree add -q --detach "$tree" "$rev"
+    printf '%-12s %s\n' "$rev" "$(measure "$tree")"
+    git -C "$repo" worktree remove --force "$tree"
+done
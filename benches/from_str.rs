@@ -0,0 +1,125 @@
+//! Compares the generated `FromStr` of a 600 variant enum, which dispatches on length and first
+//! byte, against comparing the input with every spelling in turn.
+//! Run with `cargo bench --bench from_str`.
+
+use std::{hint::black_box, str::FromStr, time::Instant};
+use to_and_fro::ToAndFro;
+
+macro_rules! codes {
+    ($($name:ident),* $(,)?) => {
+        #[derive(ToAndFro)]
+        pub enum Code {
+            $($name,)*
+        }
+
+        /// The comparison chain a plain `match` on the spellings compiles to.
+        fn linear(s: &str) -> Option<Code> {
+            $(
+                if s == stringify!($name) {
+                    return Some(Code::$name);
+                }
+            )*
+            None
+        }
+    };
+}
+
+codes! {
+    DiskFull, DiskTimeout, DiskRefused, DiskExpired, DiskCorrupt, DiskMissing, DiskBusy, DiskDenied,
+    DiskClosed, DiskStale, DiskInvalid, DiskOverflow, DiskUnderflow, DiskRetry, DiskDropped, DiskThrottled,
+    DiskUnreachable, DiskConflict, DiskLocked, DiskPartial, DiskRejected, DiskOrphaned, DiskDegraded, DiskUnknown,
+    NetFull, NetTimeout, NetRefused, NetExpired, NetCorrupt, NetMissing, NetBusy, NetDenied,
+    NetClosed, NetStale, NetInvalid, NetOverflow, NetUnderflow, NetRetry, NetDropped, NetThrottled,
+    NetUnreachable, NetConflict, NetLocked, NetPartial, NetRejected, NetOrphaned, NetDegraded, NetUnknown,
+    AuthFull, AuthTimeout, AuthRefused, AuthExpired, AuthCorrupt, AuthMissing, AuthBusy, AuthDenied,
+    AuthClosed, AuthStale, AuthInvalid, AuthOverflow, AuthUnderflow, AuthRetry, AuthDropped, AuthThrottled,
+    AuthUnreachable, AuthConflict, AuthLocked, AuthPartial, AuthRejected, AuthOrphaned, AuthDegraded, AuthUnknown,
+    CacheFull, CacheTimeout, CacheRefused, CacheExpired, CacheCorrupt, CacheMissing, CacheBusy, CacheDenied,
+    CacheClosed, CacheStale, CacheInvalid, CacheOverflow, CacheUnderflow, CacheRetry, CacheDropped, CacheThrottled,
+    CacheUnreachable, CacheConflict, CacheLocked, CachePartial, CacheRejected, CacheOrphaned, CacheDegraded, CacheUnknown,
+    QueueFull, QueueTimeout, QueueRefused, QueueExpired, QueueCorrupt, QueueMissing, QueueBusy, QueueDenied,
+    QueueClosed, QueueStale, QueueInvalid, QueueOverflow, QueueUnderflow, QueueRetry, QueueDropped, QueueThrottled,
+    QueueUnreachable, QueueConflict, QueueLocked, QueuePartial, QueueRejected, QueueOrphaned, QueueDegraded, QueueUnknown,
+    ShardFull, ShardTimeout, ShardRefused, ShardExpired, ShardCorrupt, ShardMissing, ShardBusy, ShardDenied,
+    ShardClosed, ShardStale, ShardInvalid, ShardOverflow, ShardUnderflow, ShardRetry, ShardDropped, ShardThrottled,
+    ShardUnreachable, ShardConflict, ShardLocked, ShardPartial, ShardRejected, ShardOrphaned, ShardDegraded, ShardUnknown,
+    IndexFull, IndexTimeout, IndexRefused, IndexExpired, IndexCorrupt, IndexMissing, IndexBusy, IndexDenied,
+    IndexClosed, IndexStale, IndexInvalid, IndexOverflow, IndexUnderflow, IndexRetry, IndexDropped, IndexThrottled,
+    IndexUnreachable, IndexConflict, IndexLocked, IndexPartial, IndexRejected, IndexOrphaned, IndexDegraded, IndexUnknown,
+    LockFull, LockTimeout, LockRefused, LockExpired, LockCorrupt, LockMissing, LockBusy, LockDenied,
+    LockClosed, LockStale, LockInvalid, LockOverflow, LockUnderflow, LockRetry, LockDropped, LockThrottled,
+    LockUnreachable, LockConflict, LockLocked, LockPartial, LockRejected, LockOrphaned, LockDegraded, LockUnknown,
+    TokenFull, TokenTimeout, TokenRefused, TokenExpired, TokenCorrupt, TokenMissing, TokenBusy, TokenDenied,
+    TokenClosed, TokenStale, TokenInvalid, TokenOverflow, TokenUnderflow, TokenRetry, TokenDropped, TokenThrottled,
+    TokenUnreachable, TokenConflict, TokenLocked, TokenPartial, TokenRejected, TokenOrphaned, TokenDegraded, TokenUnknown,
+    SessionFull, SessionTimeout, SessionRefused, SessionExpired, SessionCorrupt, SessionMissing, SessionBusy, SessionDenied,
+    SessionClosed, SessionStale, SessionInvalid, SessionOverflow, SessionUnderflow, SessionRetry, SessionDropped, SessionThrottled,
+    SessionUnreachable, SessionConflict, SessionLocked, SessionPartial, SessionRejected, SessionOrphaned, SessionDegraded, SessionUnknown,
+    ConfigFull, ConfigTimeout, ConfigRefused, ConfigExpired, ConfigCorrupt, ConfigMissing, ConfigBusy, ConfigDenied,
+    ConfigClosed, ConfigStale, ConfigInvalid, ConfigOverflow, ConfigUnderflow, ConfigRetry, ConfigDropped, ConfigThrottled,
+    ConfigUnreachable, ConfigConflict, ConfigLocked, ConfigPartial, ConfigRejected, ConfigOrphaned, ConfigDegraded, ConfigUnknown,
+    SchemaFull, SchemaTimeout, SchemaRefused, SchemaExpired, SchemaCorrupt, SchemaMissing, SchemaBusy, SchemaDenied,
+    SchemaClosed, SchemaStale, SchemaInvalid, SchemaOverflow, SchemaUnderflow, SchemaRetry, SchemaDropped, SchemaThrottled,
+    SchemaUnreachable, SchemaConflict, SchemaLocked, SchemaPartial, SchemaRejected, SchemaOrphaned, SchemaDegraded, SchemaUnknown,
+    SocketFull, SocketTimeout, SocketRefused, SocketExpired, SocketCorrupt, SocketMissing, SocketBusy, SocketDenied,
+    SocketClosed, SocketStale, SocketInvalid, SocketOverflow, SocketUnderflow, SocketRetry, SocketDropped, SocketThrottled,
+    SocketUnreachable, SocketConflict, SocketLocked, SocketPartial, SocketRejected, SocketOrphaned, SocketDegraded, SocketUnknown,
+    BufferFull, BufferTimeout, BufferRefused, BufferExpired, BufferCorrupt, BufferMissing, BufferBusy, BufferDenied,
+    BufferClosed, BufferStale, BufferInvalid, BufferOverflow, BufferUnderflow, BufferRetry, BufferDropped, BufferThrottled,
+    BufferUnreachable, BufferConflict, BufferLocked, BufferPartial, BufferRejected, BufferOrphaned, BufferDegraded, BufferUnknown,
+    StreamFull, StreamTimeout, StreamRefused, StreamExpired, StreamCorrupt, StreamMissing, StreamBusy, StreamDenied,
+    StreamClosed, StreamStale, StreamInvalid, StreamOverflow, StreamUnderflow, StreamRetry, StreamDropped, StreamThrottled,
+    StreamUnreachable, StreamConflict, StreamLocked, StreamPartial, StreamRejected, StreamOrphaned, StreamDegraded, StreamUnknown,
+    ThreadFull, ThreadTimeout, ThreadRefused, ThreadExpired, ThreadCorrupt, ThreadMissing, ThreadBusy, ThreadDenied,
+    ThreadClosed, ThreadStale, ThreadInvalid, ThreadOverflow, ThreadUnderflow, ThreadRetry, ThreadDropped, ThreadThrottled,
+    ThreadUnreachable, ThreadConflict, ThreadLocked, ThreadPartial, ThreadRejected, ThreadOrphaned, ThreadDegraded, ThreadUnknown,
+    TimerFull, TimerTimeout, TimerRefused, TimerExpired, TimerCorrupt, TimerMissing, TimerBusy, TimerDenied,
+    TimerClosed, TimerStale, TimerInvalid, TimerOverflow, TimerUnderflow, TimerRetry, TimerDropped, TimerThrottled,
+    TimerUnreachable, TimerConflict, TimerLocked, TimerPartial, TimerRejected, TimerOrphaned, TimerDegraded, TimerUnknown,
+    VaultFull, VaultTimeout, VaultRefused, VaultExpired, VaultCorrupt, VaultMissing, VaultBusy, VaultDenied,
+    VaultClosed, VaultStale, VaultInvalid, VaultOverflow, VaultUnderflow, VaultRetry, VaultDropped, VaultThrottled,
+    VaultUnreachable, VaultConflict, VaultLocked, VaultPartial, VaultRejected, VaultOrphaned, VaultDegraded, VaultUnknown,
+    RouteFull, RouteTimeout, RouteRefused, RouteExpired, RouteCorrupt, RouteMissing, RouteBusy, RouteDenied,
+    RouteClosed, RouteStale, RouteInvalid, RouteOverflow, RouteUnderflow, RouteRetry, RouteDropped, RouteThrottled,
+    RouteUnreachable, RouteConflict, RouteLocked, RoutePartial, RouteRejected, RouteOrphaned, RouteDegraded, RouteUnknown,
+    ProxyFull, ProxyTimeout, ProxyRefused, ProxyExpired, ProxyCorrupt, ProxyMissing, ProxyBusy, ProxyDenied,
+    ProxyClosed, ProxyStale, ProxyInvalid, ProxyOverflow, ProxyUnderflow, ProxyRetry, ProxyDropped, ProxyThrottled,
+    ProxyUnreachable, ProxyConflict, ProxyLocked, ProxyPartial, ProxyRejected, ProxyOrphaned, ProxyDegraded, ProxyUnknown,
+    QuotaFull, QuotaTimeout, QuotaRefused, QuotaExpired, QuotaCorrupt, QuotaMissing, QuotaBusy, QuotaDenied,
+    QuotaClosed, QuotaStale, QuotaInvalid, QuotaOverflow, QuotaUnderflow, QuotaRetry, QuotaDropped, QuotaThrottled,
+    QuotaUnreachable, QuotaConflict, QuotaLocked, QuotaPartial, QuotaRejected, QuotaOrphaned, QuotaDegraded, QuotaUnknown,
+    ReplicaFull, ReplicaTimeout, ReplicaRefused, ReplicaExpired, ReplicaCorrupt, ReplicaMissing, ReplicaBusy, ReplicaDenied,
+    ReplicaClosed, ReplicaStale, ReplicaInvalid, ReplicaOverflow, ReplicaUnderflow, ReplicaRetry, ReplicaDropped, ReplicaThrottled,
+    ReplicaUnreachable, ReplicaConflict, ReplicaLocked, ReplicaPartial, ReplicaRejected, ReplicaOrphaned, ReplicaDegraded, ReplicaUnknown,
+    LedgerFull, LedgerTimeout, LedgerRefused, LedgerExpired, LedgerCorrupt, LedgerMissing, LedgerBusy, LedgerDenied,
+    LedgerClosed, LedgerStale, LedgerInvalid, LedgerOverflow, LedgerUnderflow, LedgerRetry, LedgerDropped, LedgerThrottled,
+    LedgerUnreachable, LedgerConflict, LedgerLocked, LedgerPartial, LedgerRejected, LedgerOrphaned, LedgerDegraded, LedgerUnknown,
+    BatchFull, BatchTimeout, BatchRefused, BatchExpired, BatchCorrupt, BatchMissing, BatchBusy, BatchDenied,
+    BatchClosed, BatchStale, BatchInvalid, BatchOverflow, BatchUnderflow, BatchRetry, BatchDropped, BatchThrottled,
+    BatchUnreachable, BatchConflict, BatchLocked, BatchPartial, BatchRejected, BatchOrphaned, BatchDegraded, BatchUnknown,
+    CursorFull, CursorTimeout, CursorRefused, CursorExpired, CursorCorrupt, CursorMissing, CursorBusy, CursorDenied,
+    CursorClosed, CursorStale, CursorInvalid, CursorOverflow, CursorUnderflow, CursorRetry, CursorDropped, CursorThrottled,
+    CursorUnreachable, CursorConflict, CursorLocked, CursorPartial, CursorRejected, CursorOrphaned, CursorDegraded, CursorUnknown,
+}
+
+const ROUNDS: u32 = 2_000;
+
+fn measure(name: &str, inputs: &[&str], parse: impl Fn(&str) -> bool) {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for input in inputs {
+            assert!(parse(black_box(input)));
+        }
+    }
+
+    let per_parse = start.elapsed() / (ROUNDS * inputs.len() as u32);
+    println!("{name:>10}: {per_parse:?} per parse");
+}
+
+fn main() {
+    let inputs = Code::NAMES;
+
+    println!("{} variants", Code::COUNT);
+    measure("generated", inputs, |s| Code::from_str(s).is_ok());
+    measure("linear", inputs, |s| linear(s).is_some());
+}
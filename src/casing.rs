@@ -3,6 +3,7 @@ use heck::{
     AsTitleCase, AsTrainCase, AsUpperCamelCase,
 };
 use std::rc::Rc;
+use syn::{punctuated::Punctuated, Token};
 
 pub type Caser = Rc<Box<dyn Fn(&str) -> String + Send + Sync + 'static>>;
 
@@ -21,36 +22,84 @@ pub static CASES: [&str; 12] = [
     "percent",
 ];
 
-pub fn match_supplied_casing(ident: &str, attrs: &[syn::Attribute]) -> Option<Caser> {
-    let casing = attrs
-        .iter()
-        .find(|attr| attr.path().is_ident(ident))
-        .map(|attr| attr.parse_args::<syn::LitStr>().unwrap().value())
-        .or(attrs
-            .iter()
-            .find(|attr| attr.path().is_ident("casing"))
-            .map(|attr| attr.parse_args::<syn::LitStr>().unwrap().value()));
-
-    if let Some(casing) = casing {
-        match casing.as_str() {
-            "kebab" => Some(Rc::new(Box::new(|s| AsKebabCase(s).to_string()))),
-            "lower_camel" => Some(Rc::new(Box::new(|s| AsLowerCamelCase(s).to_string()))),
-            "pascal" => Some(Rc::new(Box::new(|s| AsPascalCase(s).to_string()))),
-            "shouty_kebab" => Some(Rc::new(Box::new(|s| AsShoutyKebabCase(s).to_string()))),
-            "shouty_snake" => Some(Rc::new(Box::new(|s| AsShoutySnakeCase(s).to_string()))),
-            "snake" => Some(Rc::new(Box::new(|s| AsSnakeCase(s).to_string()))),
-            "title" => Some(Rc::new(Box::new(|s| AsTitleCase(s).to_string()))),
-            "train" => Some(Rc::new(Box::new(|s| AsTrainCase(s).to_string()))),
-            "upper_camel" => Some(Rc::new(Box::new(|s| AsUpperCamelCase(s).to_string()))),
-            "upper" => Some(Rc::new(Box::new(|s| s.to_uppercase()))),
-            "lower" => Some(Rc::new(Box::new(|s| s.to_lowercase()))),
-            "percent" => Some(Rc::new(Box::new(|s| {
-                percent_encoding::utf8_percent_encode(s, percent_encoding::NON_ALPHANUMERIC)
-                    .to_string()
-            }))),
-            _ => panic!("Invalid casing {}", casing),
+fn caser_for(casing: &str) -> Option<Caser> {
+    match casing {
+        "kebab" => Some(Rc::new(Box::new(|s| AsKebabCase(s).to_string()))),
+        "lower_camel" => Some(Rc::new(Box::new(|s| AsLowerCamelCase(s).to_string()))),
+        "pascal" => Some(Rc::new(Box::new(|s| AsPascalCase(s).to_string()))),
+        "shouty_kebab" => Some(Rc::new(Box::new(|s| AsShoutyKebabCase(s).to_string()))),
+        "shouty_snake" => Some(Rc::new(Box::new(|s| AsShoutySnakeCase(s).to_string()))),
+        "snake" => Some(Rc::new(Box::new(|s| AsSnakeCase(s).to_string()))),
+        "title" => Some(Rc::new(Box::new(|s| AsTitleCase(s).to_string()))),
+        "train" => Some(Rc::new(Box::new(|s| AsTrainCase(s).to_string()))),
+        "upper_camel" => Some(Rc::new(Box::new(|s| AsUpperCamelCase(s).to_string()))),
+        "upper" => Some(Rc::new(Box::new(|s| s.to_uppercase()))),
+        "lower" => Some(Rc::new(Box::new(|s| s.to_lowercase()))),
+        "percent" => Some(Rc::new(Box::new(|s| {
+            percent_encoding::utf8_percent_encode(s, percent_encoding::NON_ALPHANUMERIC).to_string()
+        }))),
+        _ => None,
+    }
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = (above + 1)
+                .min(row[j] + 1)
+                .min(diagonal + usize::from(ca != *cb));
+            diagonal = above;
         }
-    } else {
-        None
     }
+
+    row[b.len()]
+}
+
+/// Parses a casing literal, suggesting the nearest valid case when it's misspelled.
+pub fn parse_casing(lit: &syn::LitStr) -> syn::Result<Caser> {
+    let casing = lit.value();
+    if let Some(caser) = caser_for(&casing) {
+        return Ok(caser);
+    }
+
+    let nearest = CASES
+        .iter()
+        .map(|case| (edit_distance(&casing, case), case))
+        .filter(|(distance, case)| *distance <= case.len() / 2)
+        .min();
+
+    let message = match nearest {
+        Some((_, case)) => format!("Invalid casing \"{}\", did you mean \"{}\"?", casing, case),
+        None => format!(
+            "Invalid casing \"{}\", expected one of: {}",
+            casing,
+            CASES.join(", ")
+        ),
+    };
+
+    Err(syn::Error::new(lit.span(), message))
+}
+
+/// Every casing listed in the matching attribute, falling back to `casing` when it's absent.
+pub fn match_supplied_casing(
+    ident: &str,
+    attrs: &[syn::Attribute],
+) -> syn::Result<Option<Vec<Caser>>> {
+    attrs
+        .iter()
+        .find(|attr| attr.path().is_ident(ident))
+        .or_else(|| attrs.iter().find(|attr| attr.path().is_ident("casing")))
+        .map(|attr| {
+            attr.parse_args_with(Punctuated::<syn::LitStr, Token![,]>::parse_separated_nonempty)?
+                .iter()
+                .map(parse_casing)
+                .collect()
+        })
+        .transpose()
 }
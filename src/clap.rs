@@ -0,0 +1,67 @@
+use crate::{docs::description, payload};
+use quote::quote;
+use syn::{DataEnum, Ident};
+
+/// `clap::ValueEnum`, naming each variant by its `Display` spelling, with every other spelling `FromStr`
+/// accepts as an alias. clap matches these exactly, which is why `clap` can't be combined with the parse
+/// modes accepting anything else, nor with an `input_case` that doesn't accept the `Display` spelling.
+/// Rejected variants are left out of `value_variants` and hidden, so clap neither lists nor accepts them.
+pub fn value_enum_impl(
+    enum_name: &Ident,
+    data: &DataEnum,
+    spellings: &[(Ident, Vec<String>)],
+    names: &[String],
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut values = Vec::new();
+    let mut arms = Vec::new();
+    for (variant, display) in data.variants.iter().zip(names) {
+        let pattern = payload::wildcard(enum_name, variant);
+        let Some((_, accepted)) = spellings.iter().find(|(ident, _)| *ident == variant.ident)
+        else {
+            arms.push(quote! {
+                #pattern => Some(clap::builder::PossibleValue::new(self.as_str()).hide(true)),
+            });
+            continue;
+        };
+
+        values.push(payload::construct(enum_name, variant, |_| quote!()));
+
+        if !accepted.contains(display) {
+            return Err(syn::Error::new(
+                variant.ident.span(),
+                format!(
+                    "`clap` lists values by their `Display` spelling, but `FromStr` doesn't accept `{}`; add its casing to `input_case`",
+                    display
+                ),
+            ));
+        }
+
+        let help = description(&variant.attrs)?.map(|help| quote!(.help(#help)));
+        let aliases = accepted
+            .iter()
+            .filter(|alias| *alias != display)
+            .collect::<Vec<_>>();
+        let aliases = (!aliases.is_empty()).then(|| quote!(.aliases([#(#aliases),*])));
+        arms.push(quote! {
+            #pattern => Some(clap::builder::PossibleValue::new(#display) #aliases #help),
+        });
+    }
+
+    Ok(quote! {
+        impl clap::ValueEnum for #enum_name {
+            fn value_variants<'a>() -> &'a [Self] {
+                &[#(#values),*]
+            }
+
+            fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+                match self {
+                    #(#arms)*
+                }
+            }
+
+            fn from_str(input: &str, _ignore_case: bool) -> Result<Self, String> {
+                <Self as core::str::FromStr>::from_str(input).map_err(|error| error.to_string())
+            }
+        }
+    })
+}
@@ -0,0 +1,45 @@
+use crate::mode::CaseMode;
+use proc_macro2::Span;
+use quote::quote;
+use syn::Ident;
+
+/// `parse_const`, comparing the input byte for byte against every accepted spelling, since `match`
+/// on a `&str` can't be used in a `const fn`.
+pub fn parse_const(
+    enum_name: &Ident,
+    accepted: &[(String, Span, Ident, CaseMode)],
+) -> proc_macro2::TokenStream {
+    let checks = accepted.iter().map(|(spelling, _, variant, _)| {
+        quote! {
+            if eq(s, #spelling.as_bytes()) {
+                return Some(#enum_name::#variant);
+            }
+        }
+    });
+
+    quote! {
+        /// Parses one of [`Self::PARSE_NAMES`] exactly, and can be called in const contexts.
+        /// Relaxed casing, prefixes, discriminants and the default variant are not applied.
+        pub const fn parse_const(s: &str) -> Option<Self> {
+            const fn eq(a: &[u8], b: &[u8]) -> bool {
+                if a.len() != b.len() {
+                    return false;
+                }
+
+                let mut i = 0;
+                while i < a.len() {
+                    if a[i] != b[i] {
+                        return false;
+                    }
+                    i += 1;
+                }
+
+                true
+            }
+
+            let s = s.as_bytes();
+            #(#checks)*
+            None
+        }
+    }
+}
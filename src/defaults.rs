@@ -1,42 +1,59 @@
-use quote::{format_ident, quote};
-use syn::Ident;
+use crate::error::{error_ident, error_value};
+use quote::quote;
+use syn::{Generics, Ident, Meta};
 
-pub fn get_default_variant(input_attrs: &[syn::Attribute]) -> Option<String> {
+/// The variant named by `#[default("...")]`, leaving the std `#[default]` marker on variants alone.
+pub fn get_default_variant(input_attrs: &[syn::Attribute]) -> syn::Result<Option<Ident>> {
     input_attrs
         .iter()
-        .find(|attr| attr.path().is_ident("default"))
-        .map(|attr| attr.parse_args::<syn::LitStr>().unwrap().value())
+        .find(|attr| matches!(&attr.meta, Meta::List(list) if list.path.is_ident("default")))
+        .map(|attr| attr.parse_args::<syn::LitStr>()?.parse::<Ident>())
+        .transpose()
 }
 
+/// What `from_str` returns when nothing matched. Without a default, `payload` reports the field
+/// stored in `failed` when the input matched a variant's tag but one of its fields didn't parse.
 pub fn fromstr_failure(
     enum_name: Ident,
     input_attrs: &[syn::Attribute],
-) -> proc_macro2::TokenStream {
-    match get_default_variant(input_attrs) {
-        Some(ref x) => {
-            let ident = format_ident!("{}", x);
-            quote!(Ok(#enum_name::#ident))
+    payload: bool,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let error_name = error_ident(&enum_name);
+    Ok(match get_default_variant(input_attrs)? {
+        Some(ident) => quote!(Ok(#enum_name::#ident)),
+        None if payload => {
+            let error = error_value(&enum_name);
+            quote! {
+                match failed {
+                    Some(field) => Err(#error_name {
+                        field: Some(field),
+                        ..#error
+                    }),
+                    None => Err(#error),
+                }
+            }
+        }
+        None => {
+            let error = error_value(&enum_name);
+            quote!(Err(#error))
         }
-        None => quote!(Err(anyhow::anyhow!(
-            "Invalid variant {} for enum {}",
-            s,
-            stringify!(#enum_name)
-        ))),
-    }
+    })
 }
 
-pub fn default_impl(enum_name: Ident, input_attrs: &[syn::Attribute]) -> proc_macro2::TokenStream {
-    match get_default_variant(input_attrs) {
-        Some(ref x) => {
-            let ident = format_ident!("{}", x);
-            quote! {
-                impl std::default::Default for #enum_name {
-                    fn default() -> Self {
-                        #enum_name::#ident
-                    }
+pub fn default_impl(
+    enum_name: Ident,
+    generics: &Generics,
+    input_attrs: &[syn::Attribute],
+) -> syn::Result<proc_macro2::TokenStream> {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(match get_default_variant(input_attrs)? {
+        Some(ident) => quote! {
+            impl #impl_generics core::default::Default for #enum_name #ty_generics #where_clause {
+                fn default() -> Self {
+                    #enum_name::#ident
                 }
             }
-        }
+        },
         None => quote!(),
-    }
+    })
 }
@@ -0,0 +1,137 @@
+use crate::{error::error_value, payload, should_reject};
+use quote::quote;
+use syn::{punctuated::Punctuated, DataEnum, Generics, Ident, Meta, Token, Variant};
+
+const INTEGERS: [&str; 12] = [
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// The integer type set through `#[repr(...)]`, if any.
+pub fn declared_repr(input_attrs: &[syn::Attribute]) -> syn::Result<Option<Ident>> {
+    for attr in input_attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+    {
+        let metas = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        let integer = metas.into_iter().find_map(|meta| match meta {
+            Meta::Path(path) => path
+                .get_ident()
+                .filter(|ident| INTEGERS.iter().any(|integer| *ident == integer))
+                .cloned(),
+            _ => None,
+        });
+
+        if integer.is_some() {
+            return Ok(integer);
+        }
+    }
+
+    Ok(None)
+}
+
+/// The integer type discriminants are compared as, `isize` when no `#[repr(...)]` is declared.
+pub fn repr(input_attrs: &[syn::Attribute]) -> syn::Result<Ident> {
+    Ok(declared_repr(input_attrs)?
+        .unwrap_or_else(|| Ident::new("isize", proc_macro2::Span::call_site())))
+}
+
+/// `index()` for every enum, plus `from_index()` for enums without fields, which also convert to and
+/// from their `#[repr]` type when one is declared.
+pub fn conversions(
+    enum_name: &Ident,
+    generics: &Generics,
+    data: &DataEnum,
+    repr: Option<&Ident>,
+    fieldless: bool,
+) -> proc_macro2::TokenStream {
+    let index_arms = data.variants.iter().enumerate().map(|(i, variant)| {
+        let pattern = payload::wildcard(enum_name, variant);
+        quote!(#pattern => #i,)
+    });
+
+    let index = quote! {
+        /// The position of the variant, in declaration order.
+        pub const fn index(&self) -> usize {
+            match self {
+                #(#index_arms)*
+            }
+        }
+    };
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    if !fieldless {
+        return quote! {
+            impl #impl_generics #enum_name #ty_generics #where_clause {
+                #index
+            }
+        };
+    }
+
+    let values = data
+        .variants
+        .iter()
+        .map(|variant| payload::construct(enum_name, variant, |_| quote!()))
+        .collect::<Vec<_>>();
+    let indices = 0..values.len();
+    let error_name = crate::error::error_ident(enum_name);
+    let error = error_value(enum_name);
+    let repr_conversions = repr.map(|repr| {
+        quote! {
+            impl #impl_generics core::convert::TryFrom<#repr> for #enum_name #ty_generics #where_clause {
+                type Error = #error_name;
+
+                fn try_from(value: #repr) -> Result<Self, Self::Error> {
+                    #(if value == (#values) as #repr {
+                        return Ok(#values);
+                    })*
+
+                    let s = value;
+                    Err(#error)
+                }
+            }
+
+            impl #impl_generics core::convert::From<#enum_name #ty_generics> for #repr #where_clause {
+                fn from(value: #enum_name #ty_generics) -> Self {
+                    value as #repr
+                }
+            }
+        }
+    });
+
+    quote! {
+        impl #impl_generics #enum_name #ty_generics #where_clause {
+            #index
+
+            /// The variant at `index`, in declaration order.
+            pub const fn from_index(index: usize) -> Option<Self> {
+                match index {
+                    #(#indices => Some(#values),)*
+                    _ => None,
+                }
+            }
+        }
+
+        #repr_conversions
+    }
+}
+
+/// A block returning the variant whose discriminant `s` spells, for variants opted in with `#[parse(allow_numeric)]`.
+pub fn parse(enum_name: &Ident, repr: &Ident, variants: &[&Variant]) -> proc_macro2::TokenStream {
+    let values = variants
+        .iter()
+        .filter(|variant| !should_reject(&variant.attrs))
+        .map(|variant| payload::construct(enum_name, variant, |_| quote!()))
+        .collect::<Vec<_>>();
+
+    if values.is_empty() {
+        return quote!();
+    }
+
+    quote! {
+        if let Ok(value) = s.parse::<#repr>() {
+            #(if value == (#values) as #repr {
+                return Ok(#values);
+            })*
+        }
+    }
+}
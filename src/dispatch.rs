@@ -0,0 +1,68 @@
+use proc_macro2::Literal;
+use quote::quote;
+use std::collections::BTreeMap;
+
+/// Above this many spellings, `from_str` narrows the candidates down by length and first byte before
+/// comparing strings, rather than comparing the input against every spelling in turn.
+const THRESHOLD: usize = 32;
+
+/// The `from_str` match over every exact and prefix spelling, each paired with the value it returns,
+/// running `fallback` when none of them match.
+pub fn lookup(
+    arms: &[(Vec<String>, proc_macro2::TokenStream)],
+    fallback: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let count = arms
+        .iter()
+        .map(|(spellings, _)| spellings.len())
+        .sum::<usize>();
+    if count <= THRESHOLD {
+        let arms = arms
+            .iter()
+            .map(|(spellings, value)| quote!(#(#spellings)|* => #value,));
+
+        return quote! {
+            match s {
+                #(#arms)*
+                _ => #fallback
+            }
+        };
+    }
+
+    let mut groups = BTreeMap::<(usize, Option<u8>), Vec<(&str, &proc_macro2::TokenStream)>>::new();
+    for (spellings, value) in arms {
+        for spelling in spellings {
+            groups
+                .entry((spelling.len(), spelling.bytes().next()))
+                .or_default()
+                .push((spelling, value));
+        }
+    }
+
+    let groups = groups.into_iter().map(|((len, first), arms)| {
+        let first = match first {
+            Some(byte) => {
+                let byte = Literal::u8_suffixed(byte);
+                quote!(Some(#byte))
+            }
+            None => quote!(None),
+        };
+        let (spellings, values): (Vec<_>, Vec<_>) = arms.into_iter().unzip();
+
+        quote! {
+            (#len, #first) => match s {
+                #(#spellings => return #values,)*
+                _ => {}
+            },
+        }
+    });
+
+    quote! {
+        match (s.len(), s.as_bytes().first().copied()) {
+            #(#groups)*
+            _ => {}
+        }
+
+        #fallback
+    }
+}
@@ -0,0 +1,32 @@
+use syn::{Expr, Lit, LitStr, Meta};
+
+/// The doc comment on an item, with each line trimmed and joined by spaces.
+pub fn doc_comment(attrs: &[syn::Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(meta) if meta.path.is_ident("doc") => match &meta.value {
+                Expr::Lit(expr) => match &expr.lit {
+                    Lit::Str(lit) => Some(lit.value().trim().to_string()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        })
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+
+    (!lines.is_empty()).then(|| lines.join(" "))
+}
+
+/// A variant's description, set through `description = "..."` or taken from its doc comment.
+pub fn description(attrs: &[syn::Attribute]) -> syn::Result<Option<String>> {
+    match attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("description"))
+    {
+        Some(attr) => Ok(Some(attr.parse_args::<LitStr>()?.value())),
+        None => Ok(doc_comment(attrs)),
+    }
+}
@@ -0,0 +1,296 @@
+use quote::{format_ident, quote};
+use syn::{Ident, Visibility};
+
+pub fn error_ident(enum_name: &Ident) -> Ident {
+    format_ident!("{}ParseError", enum_name)
+}
+
+pub fn error_impl(
+    enum_name: &Ident,
+    vis: &Visibility,
+    alloc: Option<&proc_macro2::TokenStream>,
+    error_trait: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let error_name = error_ident(enum_name);
+    let doc = format!(
+        "Error returned when a string can't be parsed into a [`{}`].",
+        enum_name
+    );
+
+    // Without an allocator, the input is kept inline and truncated.
+    let (storage, derives, store, fields, input, input_doc) = match alloc {
+        Some(alloc) => (
+            quote!(input: #alloc::string::String,),
+            quote!(Clone, PartialEq, Eq),
+            quote!(),
+            quote!(input: #alloc::fmt::format(input),),
+            quote!(&self.input),
+            "The input that failed to parse.",
+        ),
+        None => (
+            quote! {
+                input: [u8; 64],
+                input_len: u8,
+            },
+            quote!(Clone, Copy, PartialEq, Eq),
+            quote! {
+                struct Truncate([u8; 64], usize);
+
+                impl core::fmt::Write for Truncate {
+                    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+                        for c in s.chars() {
+                            let end = self.1 + c.len_utf8();
+                            if end > self.0.len() {
+                                return Err(core::fmt::Error);
+                            }
+                            c.encode_utf8(&mut self.0[self.1..end]);
+                            self.1 = end;
+                        }
+
+                        Ok(())
+                    }
+                }
+
+                let mut truncate = Truncate([0; 64], 0);
+                // A full buffer stops the write, leaving the input truncated.
+                let _ = core::fmt::Write::write_fmt(&mut truncate, input);
+            },
+            quote! {
+                input: truncate.0,
+                input_len: truncate.1 as u8,
+            },
+            quote!(
+                core::str::from_utf8(&self.input[..usize::from(self.input_len)])
+                    .unwrap_or_default()
+            ),
+            "The input that failed to parse, truncated to its first 64 bytes.",
+        ),
+    };
+    let inline_doc = alloc.is_none().then(|| {
+        quote!(#[doc = "The input is kept inline, truncated to its first 64 bytes, so building the error never allocates."])
+    });
+
+    quote! {
+        #[doc = #doc]
+        #inline_doc
+        #[derive(#derives)]
+        #vis struct #error_name {
+            #storage
+            expected: &'static [&'static str],
+            candidates: &'static [&'static str],
+            field: Option<&'static str>,
+        }
+
+        impl #error_name {
+            fn new(
+                input: core::fmt::Arguments,
+                expected: &'static [&'static str],
+                candidates: &'static [&'static str],
+            ) -> Self {
+                #store
+                #error_name {
+                    #fields
+                    expected,
+                    candidates,
+                    field: None,
+                }
+            }
+
+            #[doc = #input_doc]
+            pub fn input(&self) -> &str {
+                #input
+            }
+
+            /// The name of the enum that was being parsed.
+            pub fn enum_name(&self) -> &'static str {
+                stringify!(#enum_name)
+            }
+
+            /// Every spelling that would have been accepted.
+            pub fn expected(&self) -> &'static [&'static str] {
+                self.expected
+            }
+
+            /// The spellings an ambiguous prefix could stand for, empty unless the input was one.
+            pub fn candidates(&self) -> &'static [&'static str] {
+                self.candidates
+            }
+
+            /// The field that couldn't be parsed, when the input matched a tuple or struct variant's tag and delimiters.
+            pub fn field(&self) -> Option<&'static str> {
+                self.field
+            }
+
+            /// The accepted spelling closest to the input, if any is close enough to be a likely typo.
+            pub fn suggestion(&self) -> Option<&'static str> {
+                let input = self.input();
+                let len = input.chars().flat_map(char::to_lowercase).count();
+                if !self.candidates.is_empty() || self.field.is_some() || len > 64 {
+                    return None;
+                }
+
+                let mut row = [0; 65];
+                self.expected
+                    .iter()
+                    .map(|spelling| {
+                        for (j, cell) in row.iter_mut().enumerate() {
+                            *cell = j;
+                        }
+                        for (i, c) in spelling.chars().flat_map(char::to_lowercase).enumerate() {
+                            let mut diagonal = row[0];
+                            row[0] = i + 1;
+                            for (j, other) in input.chars().flat_map(char::to_lowercase).enumerate() {
+                                let above = row[j + 1];
+                                row[j + 1] = (above + 1)
+                                    .min(row[j] + 1)
+                                    .min(diagonal + usize::from(c != other));
+                                diagonal = above;
+                            }
+                        }
+
+                        (row[len], *spelling)
+                    })
+                    .filter(|(distance, spelling)| *distance <= spelling.chars().count() / 2)
+                    .min_by_key(|(distance, _)| *distance)
+                    .map(|(_, spelling)| spelling)
+            }
+        }
+
+        impl core::fmt::Debug for #error_name {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.debug_struct(stringify!(#error_name))
+                    .field("input", &self.input())
+                    .field("enum_name", &self.enum_name())
+                    .field("expected", &self.expected)
+                    .field("candidates", &self.candidates)
+                    .field("field", &self.field)
+                    .field("suggestion", &self.suggestion())
+                    .finish()
+            }
+        }
+
+        impl core::fmt::Display for #error_name {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                fn write_all(f: &mut core::fmt::Formatter, spellings: &[&str]) -> core::fmt::Result {
+                    for (i, spelling) in spellings.iter().enumerate() {
+                        if i > 0 {
+                            f.write_str(", ")?;
+                        }
+                        f.write_str(spelling)?;
+                    }
+
+                    Ok(())
+                }
+
+                if !self.candidates.is_empty() {
+                    write!(
+                        f,
+                        "Ambiguous variant {} for enum {}, could be any of: ",
+                        self.input(),
+                        self.enum_name()
+                    )?;
+                    return write_all(f, self.candidates);
+                }
+
+                if let Some(field) = self.field {
+                    return write!(
+                        f,
+                        "Invalid payload {} for enum {}, field {} couldn't be parsed",
+                        self.input(),
+                        self.enum_name(),
+                        field
+                    );
+                }
+
+                write!(f, "Invalid variant {} for enum {}", self.input(), self.enum_name())?;
+                if let Some(suggestion) = self.suggestion() {
+                    write!(f, " (did you mean {}?)", suggestion)?;
+                }
+                if !self.expected.is_empty() {
+                    f.write_str(", expected one of: ")?;
+                    write_all(f, self.expected)?;
+                }
+
+                Ok(())
+            }
+        }
+
+        impl #error_trait for #error_name {}
+    }
+}
+
+/// An error for the input `s`, listing `PARSE_NAMES` as the expected spellings rather than
+/// repeating them at every call site.
+pub fn error_value(enum_name: &Ident) -> proc_macro2::TokenStream {
+    ambiguous_value(enum_name, &[])
+}
+
+/// An error for an input matching the start of several spellings.
+pub fn ambiguous_value(enum_name: &Ident, candidates: &[String]) -> proc_macro2::TokenStream {
+    let error_name = error_ident(enum_name);
+    quote! {
+        #error_name::new(format_args!("{}", s), Self::PARSE_NAMES, &[#(#candidates),*])
+    }
+}
+
+pub fn list_error_ident(enum_name: &Ident) -> Ident {
+    format_ident!("{}ListError", enum_name)
+}
+
+pub fn list_error_impl(
+    enum_name: &Ident,
+    vis: &Visibility,
+    alloc: &proc_macro2::TokenStream,
+    error_trait: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let error_name = error_ident(enum_name);
+    let list_error_name = list_error_ident(enum_name);
+    let doc = format!(
+        "Error returned when a list of [`{}`] variants can't be parsed.",
+        enum_name
+    );
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #vis struct #list_error_name {
+            element: #alloc::string::String,
+            offset: usize,
+            source: Option<#error_name>,
+        }
+
+        impl #list_error_name {
+            /// The element that failed.
+            pub fn element(&self) -> &str {
+                &self.element
+            }
+
+            /// The byte offset of the element within the input.
+            pub fn offset(&self) -> usize {
+                self.offset
+            }
+
+            /// The error the element failed to parse with, or `None` if it was a rejected duplicate.
+            pub fn error(&self) -> Option<&#error_name> {
+                self.source.as_ref()
+            }
+        }
+
+        impl core::fmt::Display for #list_error_name {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                match &self.source {
+                    Some(error) => write!(f, "At byte {}: {}", self.offset, error),
+                    None => write!(f, "At byte {}: duplicate variant {}", self.offset, self.element),
+                }
+            }
+        }
+
+        impl #error_trait for #list_error_name {
+            fn source(&self) -> Option<&(dyn #error_trait + 'static)> {
+                self.source
+                    .as_ref()
+                    .map(|error| error as &(dyn #error_trait + 'static))
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::payload::payload_fields;
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{DataEnum, GenericParam, Generics, Ident, Lifetime, LifetimeParam, Type, WherePredicate};
+
+fn mentions(tokens: TokenStream, ident: &Ident) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(other) => other == *ident,
+        TokenTree::Group(group) => mentions(group.stream(), ident),
+        _ => false,
+    })
+}
+
+/// Whether a field's type refers to the given type parameter.
+fn uses_param(ty: &Type, param: &Ident) -> bool {
+    mentions(ty.to_token_stream(), param)
+}
+
+/// Produces the generics for each generated impl, adding the bounds the enum's fields need.
+pub struct Bounds<'a> {
+    generics: &'a Generics,
+    data: &'a DataEnum,
+    custom: Option<&'a [WherePredicate]>,
+}
+
+impl<'a> Bounds<'a> {
+    pub fn new(
+        generics: &'a Generics,
+        data: &'a DataEnum,
+        custom: Option<&'a [WherePredicate]>,
+    ) -> Self {
+        Bounds {
+            generics,
+            data,
+            custom,
+        }
+    }
+
+    /// The enum's generics, with `bound` required of every type parameter a field uses.
+    /// When `parsed_only` is set, only the fields of variants that can be parsed are considered.
+    /// Bounds supplied through `#[to_and_fro(bound = "...")]` replace the inferred ones.
+    pub fn with(&self, bound: TokenStream, parsed_only: bool) -> Generics {
+        let mut generics = self.generics.clone();
+        let predicates = match self.custom {
+            Some(custom) => custom.to_vec(),
+            None => self
+                .generics
+                .type_params()
+                .map(|param| &param.ident)
+                .filter(|param| {
+                    self.data
+                        .variants
+                        .iter()
+                        .filter(|variant| !parsed_only || !crate::should_reject(&variant.attrs))
+                        .flat_map(payload_fields)
+                        .any(|field| uses_param(&field.ty, param))
+                })
+                .map(|param| syn::parse_quote!(#param: #bound))
+                .collect(),
+        };
+
+        generics.make_where_clause().predicates.extend(predicates);
+        generics
+    }
+
+    /// A lifetime that doesn't clash with any the enum declares.
+    pub fn fresh_lifetime(&self) -> Lifetime {
+        let taken = self
+            .generics
+            .lifetimes()
+            .map(|param| param.lifetime.ident.to_string())
+            .collect::<Vec<_>>();
+
+        let name = ('a'..='z')
+            .map(String::from)
+            .find(|name| !taken.contains(name))
+            .unwrap_or_else(|| "__to_and_fro".to_string());
+
+        Lifetime::new(&format!("'{}", name), proc_macro2::Span::call_site())
+    }
+}
+
+/// `generics` with an extra lifetime parameter, for impls that borrow their input.
+pub fn with_lifetime(generics: &Generics, lifetime: &Lifetime) -> Generics {
+    let mut generics = generics.clone();
+    generics.params.insert(
+        0,
+        GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())),
+    );
+    generics
+}
+
+/// The impl parameters and where clause of `generics`, for use in an `impl` header.
+pub fn split(generics: &Generics) -> (TokenStream, TokenStream) {
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    (quote!(#impl_generics), quote!(#where_clause))
+}
@@ -1,91 +1,264 @@
-use casing::{match_supplied_casing, Caser, CASES};
-use defaults::{default_impl, fromstr_failure};
+use casing::{match_supplied_casing, Caser};
+use defaults::{default_impl, fromstr_failure, get_default_variant};
+use error::{error_ident, error_impl, list_error_impl};
+use generics::{split, with_lifetime, Bounds};
+use mode::{CaseMode, ParseMode};
+use namespace::normalize;
+use options::{Derive, Options};
 use proc_macro::TokenStream;
 use quote::{quote, ToTokens};
+use rename::{get_rename, spellings};
+use serialization::Representation;
 use std::rc::Rc;
-use syn::{parse_macro_input, punctuated::Punctuated, Data, DataEnum, DeriveInput, Ident, Variant};
+use syn::{
+    parse_macro_input, punctuated::Punctuated, spanned::Spanned, Data, DataEnum, DeriveInput,
+    Ident, Variant,
+};
+use validate::{check_collisions, validate};
 
 mod casing;
+mod clap;
+mod constant;
 mod defaults;
+mod discriminant;
+mod dispatch;
+mod docs;
+mod error;
+mod generics;
+mod list;
+mod map;
+mod mode;
+mod namespace;
+mod options;
+mod payload;
+mod prefix;
+mod rename;
+mod serialization;
+mod set;
+mod tagging;
+mod validate;
 
 fn should_reject(attrs: &[syn::Attribute]) -> bool {
     attrs.iter().any(|attr| attr.path().is_ident("reject"))
 }
 
-fn check_case(args: TokenStream) {
-    let mut args = args.into_iter();
-    if args.clone().count() != 1 {
-        panic!("Expected one argument");
-    }
-
-    let arg = args.next().unwrap();
-    if !CASES.contains(&arg.to_string().as_str()) {
-        panic!("Invalid casing {}", arg);
-    }
+fn map_variant(
+    variants: &Punctuated<Variant, syn::token::Comma>,
+    input_attrs: &[syn::Attribute],
+    case_attr: &str,
+    reject_if_present: bool,
+    mut cb: impl FnMut(&Variant, String) -> syn::Result<proc_macro2::TokenStream>,
+) -> syn::Result<Vec<proc_macro2::TokenStream>> {
+    map_variant_casings(
+        variants,
+        input_attrs,
+        case_attr,
+        reject_if_present,
+        |variant, mut cased_names| cb(variant, cased_names.remove(0)),
+    )
 }
 
-fn map_variant(
+/// Like `map_variant`, but provides the variant's name in every casing listed, without duplicates.
+fn map_variant_casings(
     variants: &Punctuated<Variant, syn::token::Comma>,
     input_attrs: &[syn::Attribute],
     case_attr: &str,
     reject_if_present: bool,
-    mut cb: impl FnMut(&Ident, String) -> proc_macro2::TokenStream,
-) -> Vec<proc_macro2::TokenStream> {
+    mut cb: impl FnMut(&Variant, Vec<String>) -> syn::Result<proc_macro2::TokenStream>,
+) -> syn::Result<Vec<proc_macro2::TokenStream>> {
     let default_caser: Caser = Rc::new(Box::new(|s| s.to_string()));
-    let default_caser = match_supplied_casing(case_attr, input_attrs).unwrap_or(default_caser);
+    let default_casers =
+        match_supplied_casing(case_attr, input_attrs)?.unwrap_or(vec![default_caser]);
 
     variants
         .iter()
         .map(|variant| {
             if reject_if_present && should_reject(&variant.attrs) {
-                return quote!();
+                return Ok(quote!());
             }
 
-            let caser =
-                match_supplied_casing(case_attr, &variant.attrs).unwrap_or(default_caser.clone());
+            let casers =
+                match_supplied_casing(case_attr, &variant.attrs)?.unwrap_or(default_casers.clone());
 
-            let variant_name = &variant.ident;
-            let cased_name = caser(variant_name.to_string().as_str());
+            let cased_names = match get_rename(&variant.attrs)? {
+                Some(rename) => vec![rename.value()],
+                None => casers.iter().fold(Vec::new(), |mut cased_names, caser| {
+                    let cased_name = caser(variant.ident.to_string().as_str());
+                    if !cased_names.contains(&cased_name) {
+                        cased_names.push(cased_name);
+                    }
+                    cased_names
+                }),
+            };
 
-            cb(variant_name, cased_name)
+            cb(variant, cased_names)
         })
         .collect()
 }
 
-fn preamble(input: DeriveInput) -> (DeriveInput, Ident, DataEnum) {
+fn preamble(input: DeriveInput) -> syn::Result<(DeriveInput, Ident, DataEnum)> {
     let name = input.clone().ident;
     let data = match input.clone().data {
         Data::Enum(data) => data,
-        _ => panic!("Display can only be implemented for enums"),
+        Data::Struct(data) => {
+            return Err(syn::Error::new(
+                data.struct_token.span(),
+                "ToAndFro can only be derived for enums",
+            ))
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span(),
+                "ToAndFro can only be derived for enums",
+            ))
+        }
     };
 
-    (input, name, data)
+    Ok((input, name, data))
 }
 
 /// Generate automatic implementations of `FromStr`, `TryFrom<str-like>`, `Display`, `Debug`, `PartialEq`, `Eq` and `Hash` for an enum.
+/// Parse failures are reported through a generated `<Enum>ParseError` type.
+/// Options are given through `#[to_and_fro(...)]`, e.g. `#[to_and_fro(casing = "kebab", default = "Foo", serde)]`.
 #[proc_macro_derive(
     ToAndFro,
-    attributes(input_case, output_case, default, reject, casing, serde)
+    attributes(
+        input_case,
+        output_case,
+        default,
+        reject,
+        casing,
+        serde,
+        payload,
+        rename,
+        alias,
+        parse,
+        to_and_fro
+    )
 )]
 pub fn tf_derive(input: TokenStream) -> TokenStream {
-    let (input, name, data) = preamble(parse_macro_input!(input as DeriveInput));
+    expand(parse_macro_input!(input as DeriveInput))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-    // Generated based on default attr
-    let from_str_failure = fromstr_failure(name.clone(), &input.attrs);
-    let default_impl = default_impl(name.clone(), &input.attrs);
+fn expand(mut input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let deprecations = normalize(&mut input)?;
+    let (input, name, data) = preamble(input)?;
+    validate(&input, &data)?;
+
+    let options = Options::parse(&input.attrs)?;
+    let bounds = Bounds::new(&input.generics, &data, options.bound.as_deref());
+    let alloc = options.alloc_crate();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Without a default, a field that fails to parse is named in the error
+    let reports_field = get_default_variant(&input.attrs)?.is_none();
 
     // Generated based on variants
-    let from_str_arms = map_variant(
+    let mut accepted = Vec::new();
+    let mut relaxed_parsers = Vec::new();
+    let mut payload_parsers = Vec::new();
+    let mut prefixed = Vec::new();
+    let mut string_arms = Vec::new();
+    map_variant_casings(
         &data.variants,
         &input.attrs,
         "input_case",
         true,
-        |variant_name, cased_name| {
-            quote! {
-                #cased_name => Ok(#name::#variant_name),
+        |variant, cased_names| {
+            let mode = ParseMode::resolve(&input.attrs, &variant.attrs)?;
+            let case = mode.case;
+            let spellings = spellings(variant, cased_names)?;
+            let names = spellings
+                .iter()
+                .map(|(spelling, _)| spelling)
+                .collect::<Vec<_>>();
+            accepted.extend(
+                spellings
+                    .iter()
+                    .map(|(spelling, span)| (spelling.clone(), *span, variant.ident.clone(), case)),
+            );
+
+            if payload::has_fields(variant) {
+                for spelling in names {
+                    payload_parsers.push(payload::parse(
+                        &name,
+                        &input.attrs,
+                        variant,
+                        spelling,
+                        case,
+                        reports_field,
+                    )?);
+                }
+                return Ok(quote!());
+            }
+
+            let value = payload::construct(&name, variant, |_| quote!(core::marker::PhantomData));
+            if mode.prefix {
+                for spelling in &names {
+                    prefixed.push((spelling.to_string(), variant.ident.clone(), value.clone()));
+                }
+            }
+
+            if case != CaseMode::Exact {
+                for spelling in &names {
+                    let matches = case.compare(quote!(s), spelling);
+                    relaxed_parsers.push(quote! {
+                        if #matches {
+                            return Ok(#value);
+                        }
+                    });
+                }
             }
+
+            let names = names.into_iter().cloned().collect();
+            string_arms.push((names, quote!(Ok(#value))));
+            Ok(quote!())
         },
-    );
+    )?;
+
+    check_collisions(&accepted)?;
+    let parse_const = data
+        .variants
+        .iter()
+        .all(|variant| variant.fields.is_empty())
+        .then(|| constant::parse_const(&name, &accepted));
+    let words_eq = accepted
+        .iter()
+        .any(|(_, _, _, case)| *case == CaseMode::AnyCase)
+        .then(mode::words_eq);
+    // Every spelling each variant accepts, in declaration order
+    let variant_spellings = data
+        .variants
+        .iter()
+        .map(|variant| {
+            accepted
+                .iter()
+                .filter(|(_, _, ident, _)| *ident == variant.ident)
+                .map(|(spelling, _, _, _)| spelling.clone())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    string_arms.extend(prefix::prefix_arms(&name, &prefixed, &accepted));
+    let expected = accepted
+        .into_iter()
+        .map(|(spelling, _, _, _)| spelling)
+        .collect::<Vec<_>>();
+
+    // Generated based on default attr
+    let reports_field = reports_field && !payload_parsers.is_empty();
+    let failed = reports_field.then(|| quote!(let mut failed = None;));
+    let from_str_failure = fromstr_failure(name.clone(), &input.attrs, reports_field)?;
+    let default_impl = default_impl(name.clone(), &input.generics, &input.attrs)?;
+
+    let error_name = error_ident(&name);
+    let error_trait = options.error_trait();
+    let error_impl = error_impl(&name, &input.vis, alloc.as_ref(), &error_trait);
+    let list_error_impl = alloc
+        .as_ref()
+        .map(|alloc| list_error_impl(&name, &input.vis, alloc, &error_trait));
 
     // Generated based on variants
     let dbg_arms = map_variant(
@@ -93,327 +266,572 @@ pub fn tf_derive(input: TokenStream) -> TokenStream {
         &input.attrs,
         "output_case",
         false,
-        |variant_name, _| {
-            quote! {
-                #name::#variant_name => {
-                    f.write_str(stringify!(#name))?;
-                    f.write_str("::")?;
-                    f.write_str(stringify!(#variant_name))
-                },
+        |variant, _| {
+            let variant_name = &variant.ident;
+            if !payload::has_fields(variant) {
+                let pattern = payload::wildcard(&name, variant);
+                return Ok(quote! {
+                    #pattern => {
+                        f.write_str(stringify!(#name))?;
+                        f.write_str("::")?;
+                        f.write_str(stringify!(#variant_name))
+                    },
+                });
             }
+
+            let pattern = payload::pattern(&name, variant, "f");
+            let bindings = variant
+                .fields
+                .members()
+                .zip(payload::bindings(variant, "f"));
+            let fields = match variant.fields {
+                syn::Fields::Named(_) => bindings
+                    .map(|(member, binding)| quote!(.field(stringify!(#member), #binding)))
+                    .collect::<Vec<_>>(),
+                _ => bindings
+                    .map(|(_, binding)| quote!(.field(#binding)))
+                    .collect(),
+            };
+
+            let debug = match variant.fields {
+                syn::Fields::Named(_) => quote!(debug_struct),
+                _ => quote!(debug_tuple),
+            };
+
+            Ok(quote! {
+                #pattern => f
+                    .#debug(concat!(stringify!(#name), "::", stringify!(#variant_name)))
+                    #(#fields)*
+                    .finish(),
+            })
         },
-    );
+    )?;
 
     // Generated based on variants
+    let mut descriptions = Vec::new();
+    let description_arms = data
+        .variants
+        .iter()
+        .map(|variant| {
+            let pattern = payload::wildcard(&name, variant);
+            let description = match docs::description(&variant.attrs)? {
+                Some(description) => quote!(Some(#description)),
+                None => quote!(None),
+            };
+            descriptions.push(description.clone());
+            Ok(quote!(#pattern => #description,))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let mut output_names = Vec::new();
     let str_arms = map_variant(
         &data.variants,
         &input.attrs,
         "output_case",
         false,
-        |variant_name, cased_name| {
-            quote! {
-                #name::#variant_name => #cased_name,
+        |variant, cased_name| {
+            let pattern = payload::wildcard(&name, variant);
+            output_names.push(cased_name.clone());
+            Ok(quote! {
+                #pattern => #cased_name,
+            })
+        },
+    )?;
+
+    let clap_impl = options.clap.then(|| {
+        let spellings = data
+            .variants
+            .iter()
+            .zip(&variant_spellings)
+            .map(|(variant, names)| (variant.ident.clone(), names.clone()))
+            .filter(|(_, names)| !names.is_empty())
+            .collect::<Vec<_>>();
+        clap::value_enum_impl(&name, &data, &spellings, &output_names)
+    });
+    let clap_impl = clap_impl.transpose()?;
+
+    // Generated based on variants with fields
+    let display_arms = map_variant(
+        &data.variants,
+        &input.attrs,
+        "output_case",
+        false,
+        |variant, cased_name| {
+            if !payload::has_fields(variant) {
+                let pattern = payload::wildcard(&name, variant);
+                return Ok(quote! {
+                    #pattern => f.write_str(#cased_name),
+                });
             }
+
+            let pattern = payload::pattern(&name, variant, "f");
+            let payload = payload::display(&input.attrs, variant)?;
+            Ok(quote! {
+                #pattern => {
+                    f.write_str(#cased_name)?;
+                    #payload
+                },
+            })
         },
-    );
+    )?;
 
     let clone_arms = map_variant(
         &data.variants,
         &input.attrs,
         "output_case",
         false,
-        |variant_name, _| {
-            quote! {
-                #name::#variant_name => #name::#variant_name,
-            }
+        |variant, _| {
+            let pattern = payload::pattern(&name, variant, "f");
+            let value = payload::construct(
+                &name,
+                variant,
+                |binding| quote!(core::clone::Clone::clone(#binding)),
+            );
+            Ok(quote! {
+                #pattern => #value,
+            })
         },
-    );
+    )?;
+
+    let eq_arms = map_variant(
+        &data.variants,
+        &input.attrs,
+        "output_case",
+        false,
+        |variant, _| {
+            let lhs = payload::pattern(&name, variant, "a");
+            let rhs = payload::pattern(&name, variant, "b");
+            let eqs = payload::bindings(variant, "a")
+                .into_iter()
+                .zip(payload::bindings(variant, "b"))
+                .map(|(a, b)| quote!(&& #a == #b));
+            Ok(quote! {
+                (#lhs, #rhs) => true #(#eqs)*,
+            })
+        },
+    )?;
+
+    let hash_arms = map_variant(
+        &data.variants,
+        &input.attrs,
+        "output_case",
+        false,
+        |variant, _| {
+            let pattern = payload::pattern(&name, variant, "f");
+            let hashes = payload::bindings(variant, "f");
+            Ok(quote! {
+                #pattern => {
+                    #(core::hash::Hash::hash(#hashes, state);)*
+                }
+            })
+        },
+    )?;
+
+    let assertions = map_variant(
+        &data.variants,
+        &input.attrs,
+        "output_case",
+        false,
+        |variant, _| {
+            Ok(payload::assert_round_trip(
+                variant,
+                !should_reject(&variant.attrs),
+            ))
+        },
+    )?;
 
     let variant_count = data.variants.len();
-    let variants = data.variants.iter().map(|v| v.ident.to_token_stream());
+    let variants = data
+        .variants
+        .iter()
+        .map(|v| v.ident.to_token_stream())
+        .collect::<Vec<_>>();
 
-    // Serde impl
-    let serde_impl = &input
-        .attrs
+    // Rejected variants are never read back, whether from a string or an integer
+    let readable = data
+        .variants
+        .iter()
+        .enumerate()
+        .filter(|(_, variant)| !should_reject(&variant.attrs))
+        .map(|(i, variant)| (i, variant.ident.to_token_stream()))
+        .collect::<Vec<_>>();
+
+    let display_generics = bounds.with(quote!(core::fmt::Display), false);
+    let (display_params, display_where) = split(&display_generics);
+    let from_str_generics = bounds.with(quote!(core::str::FromStr), true);
+    let (from_str_params, from_str_where) = split(&from_str_generics);
+
+    let fieldless = data.variants.iter().all(|v| v.fields.is_empty());
+
+    let repr = discriminant::repr(&input.attrs)?;
+    let variant_modes = data
+        .variants
+        .iter()
+        .map(|variant| ParseMode::resolve(&input.attrs, &variant.attrs))
+        .collect::<syn::Result<Vec<_>>>()?;
+    let numeric_variants = readable
         .iter()
-        .find(|attr| attr.path().is_ident("serde"))
-        .map(|_| {
+        .filter(|(i, _)| variant_modes[*i].numeric)
+        .map(|(_, variant)| variant.clone())
+        .collect::<Vec<_>>();
+
+    // Serde impl
+    let representation = Representation::resolve(&input.attrs, &options)?;
+    let serde = representation.is_some();
+    let serde_impl = representation
+        .map(|representation| {
+            if let Representation::Tagged(tagging) = &representation {
+                let spellings = tagging::Spellings {
+                    names: &output_names,
+                    accepted: &variant_spellings,
+                    modes: &variant_modes,
+                    repr: &repr,
+                };
+                return tagging::serde_impl(
+                    &name,
+                    &data,
+                    &bounds,
+                    tagging,
+                    &spellings,
+                    alloc.as_ref(),
+                );
+            }
+
+            let deserialize_generics =
+                with_lifetime(&from_str_generics, &syn::parse_quote!('de));
+            let (deserialize_params, _) = split(&deserialize_generics);
+            let serialize =
+                serialization::serialize(&name, &variants, &repr, &representation, fieldless);
+            let deserialize =
+                serialization::deserialize(
+                    &name,
+                    &readable,
+                    &numeric_variants,
+                    &repr,
+                    &representation,
+                    fieldless,
+                );
+
             quote! {
-                impl serde::Serialize for #name {
+                impl #display_params serde::Serialize for #name #ty_generics #display_where {
                     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
                     where
                         S: serde::Serializer,
                     {
-                        serializer.serialize_str(&self.to_string())
+                        #serialize
                     }
                 }
 
-                impl<'de> serde::Deserialize<'de> for #name {
+                impl #deserialize_params serde::Deserialize<'de> for #name #ty_generics #from_str_where {
                     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
                     where
                         D: serde::Deserializer<'de>,
                     {
-                        let s = String::deserialize(deserializer)?;
-                        Self::from_str(&s).map_err(serde::de::Error::custom)
+                        #deserialize
                     }
                 }
             }
         })
         .unwrap_or(quote!());
 
-    // only allow list() on enums that don't have fields
-    let list = if data.variants.iter().all(|v| v.fields.is_empty()) {
+    // only allow list(), iter() and ALL on enums that don't have fields
+    let list = if fieldless {
         quote!(
-            impl #name {
-                pub fn list() -> [#name; #variant_count] {
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Every variant, in declaration order.
+                pub const ALL: &'static [Self] = &[#( #name::#variants, )*];
+
+                pub const fn list() -> [Self; #variant_count] {
                     [
                     #( #name::#variants, )*
                     ]
                 }
+
+                /// Iterates over every variant, in declaration order.
+                pub fn iter() -> core::array::IntoIter<Self, #variant_count> {
+                    Self::list().into_iter()
+                }
+
+                #parse_const
             }
         )
     } else {
         quote!()
     };
 
-    let expanded = quote! {
+    let implements = |derive| options.implements(derive, fieldless);
 
-        #default_impl
-        #serde_impl
-        #list
+    let declared_repr = discriminant::declared_repr(&input.attrs)?;
+    let conversions = discriminant::conversions(
+        &name,
+        &input.generics,
+        &data,
+        declared_repr.as_ref(),
+        fieldless,
+    );
+    let numeric = data
+        .variants
+        .iter()
+        .zip(&variant_modes)
+        .filter(|(_, mode)| mode.numeric)
+        .map(|(variant, _)| variant)
+        .collect::<Vec<_>>();
+    let numeric_parser = discriminant::parse(&name, &repr, &numeric);
+    let lookup = dispatch::lookup(
+        &string_arms,
+        quote!({
+            #words_eq
+            #(#relaxed_parsers)*
+            #numeric_parser
+            #failed
+            #(#payload_parsers)*
+            #from_str_failure
+        }),
+    );
 
-        impl #name {
-            pub fn as_str(&self) -> &'static str {
-                match self {
-                    #(#str_arms)*
-                }
+    let set_impl = options.set.as_ref().map(|separator| {
+        set::set_impl(
+            &name,
+            &input.vis,
+            variant_count,
+            separator,
+            options.list.trim,
+        )
+    });
+    let list_impls = alloc.as_ref().map(|alloc| {
+        let parse_list = list::parse_list(&name, &options.list, alloc);
+        let join = list::join(!fieldless, alloc);
+        quote! {
+            impl #from_str_params #name #ty_generics #from_str_where {
+                #parse_list
             }
-        }
 
-        impl Clone for #name {
-            fn clone(&self) -> #name {
-                match self {
-                    #(#clone_arms)*
-                }
+            impl #display_params #name #ty_generics #display_where {
+                #join
             }
         }
+    });
 
-        impl Copy for #name {}
+    let map_impl = options
+        .map
+        .then(|| map::map_impl(&name, &input.vis, variant_count, serde));
 
-        impl std::cmp::PartialEq for #name {
-            fn eq(&self, other: &Self) -> bool {
-                std::mem::discriminant(self) == std::mem::discriminant(other)
-            }
+    let assertions = if fieldless {
+        quote!()
+    } else {
+        let assert_generics = bounds.with(quote!(core::str::FromStr + core::fmt::Display), false);
+        let (assert_params, assert_where) = split(&assert_generics);
+        quote! {
+            const _: () = {
+                fn assert_from_str<T: core::str::FromStr>() {}
+                fn assert_display<T: core::fmt::Display>() {}
+
+                #[allow(dead_code)]
+                fn assert_fields #assert_params () #assert_where {
+                    #(#assertions)*
+                }
+            };
         }
+    };
 
-        impl std::cmp::Eq for #name {}
-
-        impl std::hash::Hash for #name {
-            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-                std::mem::discriminant(self).hash(state)
+    let clone_impl = implements(Derive::Clone).then(|| {
+        let (params, where_clause) = split(&bounds.with(quote!(core::clone::Clone), false));
+        quote! {
+            impl #params Clone for #name #ty_generics #where_clause {
+                fn clone(&self) -> Self {
+                    match self {
+                        #(#clone_arms)*
+                    }
+                }
             }
         }
-
-        impl std::fmt::Display for #name {
-            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-                f.write_str(self.as_str())
+    });
+
+    let copy_impl = implements(Derive::Copy).then(|| {
+        let (params, where_clause) = split(&bounds.with(quote!(core::marker::Copy), false));
+        quote!(impl #params Copy for #name #ty_generics #where_clause {})
+    });
+
+    // Enums without fields compare, hash and display by discriminant alone
+    let partial_eq_generics = bounds.with(quote!(core::cmp::PartialEq), false);
+    let (partial_eq_params, partial_eq_where) = split(&partial_eq_generics);
+    let partial_eq_impl = implements(Derive::PartialEq).then(|| match fieldless {
+        true => quote! {
+            impl #partial_eq_params core::cmp::PartialEq for #name #ty_generics #partial_eq_where {
+                fn eq(&self, other: &Self) -> bool {
+                    core::mem::discriminant(self) == core::mem::discriminant(other)
+                }
             }
-        }
-
-        impl std::fmt::Debug for #name {
-            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-                match self {
-                    #(#dbg_arms)*
+        },
+        false => quote! {
+            impl #partial_eq_params core::cmp::PartialEq for #name #ty_generics #partial_eq_where {
+                fn eq(&self, other: &Self) -> bool {
+                    #[allow(unreachable_patterns)]
+                    match (self, other) {
+                        #(#eq_arms)*
+                        _ => false,
+                    }
                 }
             }
-        }
-
-        impl std::str::FromStr for #name {
-            type Err = anyhow::Error;
-
-            fn from_str(s: &str) -> Result<Self, Self::Err> {
-                match s {
-                    #(#from_str_arms)*
-                    _ => #from_str_failure
+        },
+    });
+
+    let eq_impl = implements(Derive::Eq).then(|| {
+        let (params, where_clause) = split(&bounds.with(quote!(core::cmp::Eq), false));
+        quote!(impl #params core::cmp::Eq for #name #ty_generics #where_clause {})
+    });
+
+    let hash_generics = bounds.with(quote!(core::hash::Hash), false);
+    let (hash_params, hash_where) = split(&hash_generics);
+    let hash_impl = implements(Derive::Hash).then(|| match fieldless {
+        true => quote! {
+            impl #hash_params core::hash::Hash for #name #ty_generics #hash_where {
+                fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                    core::mem::discriminant(self).hash(state)
                 }
             }
-        }
-
-        impl std::convert::TryFrom<std::rc::Rc<str>> for #name {
-            type Error = anyhow::Error;
-
-            fn try_from(s: std::rc::Rc<str>) -> Result<Self, Self::Error> {
-                s.parse()
+        },
+        false => quote! {
+            impl #hash_params core::hash::Hash for #name #ty_generics #hash_where {
+                fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                    core::hash::Hash::hash(&core::mem::discriminant(self), state);
+                    match self {
+                        #(#hash_arms)*
+                    }
+                }
+            }
+        },
+    });
+
+    let debug_impl = implements(Derive::Debug).then(|| {
+        let (params, where_clause) = split(&bounds.with(quote!(core::fmt::Debug), false));
+        quote! {
+            impl #params core::fmt::Debug for #name #ty_generics #where_clause {
+                fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                    match self {
+                        #(#dbg_arms)*
+                    }
+                }
             }
         }
+    });
 
-        impl<'a> std::convert::TryFrom<&'a std::rc::Rc<str>> for #name {
-            type Error = anyhow::Error;
-
-            fn try_from(s: &'a std::rc::Rc<str>) -> Result<Self, Self::Error> {
-                s.parse()
+    let display_impl = match fieldless {
+        true => quote! {
+            impl #display_params core::fmt::Display for #name #ty_generics #display_where {
+                fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                    f.write_str(self.as_str())
+                }
             }
-        }
+        },
+        false => quote! {
+            impl #display_params core::fmt::Display for #name #ty_generics #display_where {
+                fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                    match self {
+                        #(#display_arms)*
+                    }
+                }
+            }
+        },
+    };
+
+    let lifetime = bounds.fresh_lifetime();
+    let (borrowed_params, _) = split(&with_lifetime(&from_str_generics, &lifetime));
+    // `parse` covers every other string type, so only `&str` and, with an allocator, `String`
+    // keep a `TryFrom` impl.
+    let owned = alloc.iter().flat_map(|alloc| {
+        [
+            (quote!(#alloc::string::String), false),
+            (quote!(&#lifetime #alloc::string::String), true),
+        ]
+    });
+    let try_from_impls = owned
+        .chain([(quote!(&#lifetime str), true)])
+        .map(|(source, borrowed)| {
+            let params = match borrowed {
+                true => &borrowed_params,
+                false => &from_str_params,
+            };
 
-        impl std::convert::TryFrom<std::sync::Arc<str>> for #name {
-            type Error = anyhow::Error;
+            quote! {
+                impl #params core::convert::TryFrom<#source> for #name #ty_generics #from_str_where {
+                    type Error = #error_name;
 
-            fn try_from(s: std::sync::Arc<str>) -> Result<Self, Self::Error> {
-                s.parse()
+                    fn try_from(s: #source) -> Result<Self, Self::Error> {
+                        s.parse()
+                    }
+                }
             }
-        }
+        });
 
-        impl<'a> std::convert::TryFrom<&'a std::sync::Arc<str>> for #name {
-            type Error = anyhow::Error;
+    let expanded = quote! {
+        #(#deprecations)*
 
-            fn try_from(s: &'a std::sync::Arc<str>) -> Result<Self, Self::Error> {
-                s.parse()
-            }
-        }
+        #default_impl
+        #error_impl
+        #list_error_impl
+        #serde_impl
+        #list
 
-        impl std::convert::TryFrom<std::boxed::Box<str>> for #name {
-            type Error = anyhow::Error;
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// The number of variants.
+            pub const COUNT: usize = #variant_count;
 
-            fn try_from(s: std::boxed::Box<str>) -> Result<Self, Self::Error> {
-                s.parse()
-            }
-        }
+            /// The name of every variant as written by `Display`, in declaration order.
+            pub const NAMES: &'static [&'static str] = &[#(#output_names),*];
 
-        impl<'a> std::convert::TryFrom<&'a std::boxed::Box<str>> for #name {
-            type Error = anyhow::Error;
+            /// Every string accepted when parsing.
+            pub const PARSE_NAMES: &'static [&'static str] = &[#(#expected),*];
 
-            fn try_from(s: &'a std::boxed::Box<str>) -> Result<Self, Self::Error> {
-                s.parse()
-            }
-        }
+            /// The description of every variant, in declaration order.
+            pub const DESCRIPTIONS: &'static [Option<&'static str>] = &[#(#descriptions),*];
 
-        impl std::convert::TryFrom<String> for #name {
-            type Error = anyhow::Error;
+            /// The variant's description, from `#[to_and_fro(description = "...")]` or its doc comment.
+            pub const fn description(&self) -> Option<&'static str> {
+                match self {
+                    #(#description_arms)*
+                }
+            }
 
-            fn try_from(s: String) -> Result<Self, Self::Error> {
-                s.parse()
+            pub const fn as_str(&self) -> &'static str {
+                match self {
+                    #(#str_arms)*
+                }
             }
         }
 
-        impl<'a> std::convert::TryFrom<&'a String> for #name {
-            type Error = anyhow::Error;
+        #conversions
+        #set_impl
+        #map_impl
+        #clap_impl
+        #assertions
+        #clone_impl
+        #copy_impl
+        #partial_eq_impl
+        #eq_impl
+        #hash_impl
+        #debug_impl
+        #display_impl
+
+        impl #from_str_params core::str::FromStr for #name #ty_generics #from_str_where {
+            type Err = #error_name;
 
-            fn try_from(s: &'a String) -> Result<Self, Self::Error> {
-                s.parse()
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                #lookup
             }
         }
 
-        impl<'a> std::convert::TryFrom<&'a str> for #name {
-            type Error = anyhow::Error;
-
-            fn try_from(s: &'a str) -> Result<Self, Self::Error> {
-                s.parse()
+        impl #from_str_params #name #ty_generics #from_str_where {
+            /// Parses anything that can be borrowed as a `str`, such as a `String`, `Box<str>`, `Rc<str>` or `Cow<str>`.
+            pub fn parse(s: impl AsRef<str>) -> Result<Self, #error_name> {
+                <Self as core::str::FromStr>::from_str(s.as_ref())
             }
         }
-    };
-
-    TokenStream::from(expanded)
-}
 
-/// Define the default case to expect for both parsing, or stringifying.
-/// Valid values are:
-/// - `kebab` [(heck)](https://docs.rs/heck/latest/heck/struct.AsKebabCase.html)
-/// - `pascal` [(heck)](https://docs.rs/heck/latest/heck/struct.AsPascalCase.html)
-/// - `snake` [(heck)](https://docs.rs/heck/latest/heck/struct.AsSnakeCase.html)
-/// - `title` [(heck)](https://docs.rs/heck/latest/heck/struct.AsTitleCase.html)
-/// - `train` [(heck)](https://docs.rs/heck/latest/heck/struct.AsTrainCase.html)
-/// - `lower_camel` [(heck)](https://docs.rs/heck/latest/heck/struct.AsLowerCamelCase.html)
-/// - `upper_camel` [(heck)](https://docs.rs/heck/latest/heck/struct.AsUpperCamelCase.html)
-/// - `shouty_kebab` [(heck)](https://docs.rs/heck/latest/heck/struct.AsShoutyKebabCase.html)
-/// - `shouty_snake` [(heck)](https://docs.rs/heck/latest/heck/struct.AsShoutySnakeCase.html)
-/// - `upper` (UPPERCASE)
-/// - `lower` (lowercase)
-/// - percent
-#[proc_macro_attribute]
-pub fn casing(args: TokenStream, input: TokenStream) -> TokenStream {
-    check_case(args);
-    input
-}
-
-/// Define the case to expect when parsing a variant from a string.
-/// Valid values are:
-/// - `kebab` [(heck)](https://docs.rs/heck/latest/heck/struct.AsKebabCase.html)
-/// - `pascal` [(heck)](https://docs.rs/heck/latest/heck/struct.AsPascalCase.html)
-/// - `snake` [(heck)](https://docs.rs/heck/latest/heck/struct.AsSnakeCase.html)
-/// - `title` [(heck)](https://docs.rs/heck/latest/heck/struct.AsTitleCase.html)
-/// - `train` [(heck)](https://docs.rs/heck/latest/heck/struct.AsTrainCase.html)
-/// - `lower_camel` [(heck)](https://docs.rs/heck/latest/heck/struct.AsLowerCamelCase.html)
-/// - `upper_camel` [(heck)](https://docs.rs/heck/latest/heck/struct.AsUpperCamelCase.html)
-/// - `shouty_kebab` [(heck)](https://docs.rs/heck/latest/heck/struct.AsShoutyKebabCase.html)
-/// - `shouty_snake` [(heck)](https://docs.rs/heck/latest/heck/struct.AsShoutySnakeCase.html)
-/// - `upper` (UPPERCASE)
-/// - `lower` (lowercase)
-/// - percent
-#[proc_macro_attribute]
-pub fn input_case(args: TokenStream, input: TokenStream) -> TokenStream {
-    check_case(args);
-    input
-}
+        #(#try_from_impls)*
 
-/// Define the case to stringify to through Display, or Debug.
-/// Valid values are:
-/// - `kebab` [(heck)](https://docs.rs/heck/latest/heck/struct.AsKebabCase.html)
-/// - `pascal` [(heck)](https://docs.rs/heck/latest/heck/struct.AsPascalCase.html)
-/// - `snake` [(heck)](https://docs.rs/heck/latest/heck/struct.AsSnakeCase.html)
-/// - `title` [(heck)](https://docs.rs/heck/latest/heck/struct.AsTitleCase.html)
-/// - `train` [(heck)](https://docs.rs/heck/latest/heck/struct.AsTrainCase.html)
-/// - `lower_camel` [(heck)](https://docs.rs/heck/latest/heck/struct.AsLowerCamelCase.html)
-/// - `upper_camel` [(heck)](https://docs.rs/heck/latest/heck/struct.AsUpperCamelCase.html)
-/// - `shouty_kebab` [(heck)](https://docs.rs/heck/latest/heck/struct.AsShoutyKebabCase.html)
-/// - `shouty_snake` [(heck)](https://docs.rs/heck/latest/heck/struct.AsShoutySnakeCase.html)
-/// - `upper` (UPPERCASE)
-/// - `lower` (lowercase)
-/// - percent
-#[proc_macro_attribute]
-pub fn output_case(args: TokenStream, input: TokenStream) -> TokenStream {
-    check_case(args);
-    input
-}
-
-/// Defines the field to default to when parsing fails.
-/// Also generates a `Default` implimentation pointing to the default variant.
-/// ```rs
-/// #[derive(ToAndFro)]
-/// #[default("Load")]
-/// pub enum TestEnum {
-///   Generation,
-///   Load,
-///   Customers,
-/// }
-///
-/// assert_eq!(TestEnum::from_str("Uncaught Case").unwrap(), TestEnum::Load);
-/// ```
-#[proc_macro_attribute]
-pub fn default(args: TokenStream, input: TokenStream) -> TokenStream {
-    if args.clone().into_iter().count() != 1 {
-        panic!("#[default(\"...\")] takes one argument");
-    }
-
-    input
-}
-
-/// Rejects the variant from being parsed from a String.
-/// This either throws an Error on parse, or defaults to the variant specified with `default`.
-#[proc_macro_attribute]
-pub fn reject(args: TokenStream, input: TokenStream) -> TokenStream {
-    if !args.is_empty() {
-        panic!("#[reject] does not take arguments");
-    }
-
-    input
-}
-
-/// Impliments `serde::Serialize` and `serde::Deserialize` for the enum.
-#[proc_macro_attribute]
-pub fn serde(args: TokenStream, input: TokenStream) -> TokenStream {
-    if !args.is_empty() {
-        panic!("#[serde] does not take arguments");
-    }
+        #list_impls
+    };
 
-    input
+    Ok(expanded)
 }
@@ -0,0 +1,97 @@
+use crate::{
+    error::list_error_ident,
+    options::{Duplicates, List},
+};
+use quote::quote;
+use syn::Ident;
+
+/// `parse_list`, splitting on a separator and parsing every element through `FromStr`.
+pub fn parse_list(
+    enum_name: &Ident,
+    options: &List,
+    alloc: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let list_error_name = list_error_ident(enum_name);
+    let (input, element) = match options.trim {
+        true => (
+            quote!(s.trim()),
+            quote! {
+                let start = start + (part.len() - part.trim_start().len());
+                let part = part.trim();
+            },
+        ),
+        false => (quote!(s), quote!()),
+    };
+
+    let duplicate = match options.duplicates {
+        Duplicates::Allow => quote!(),
+        Duplicates::Ignore => quote! {
+            if items.iter().any(|item: &Self| item.index() == value.index()) {
+                continue;
+            }
+        },
+        Duplicates::Reject => quote! {
+            if items.iter().any(|item: &Self| item.index() == value.index()) {
+                return Err(#list_error_name {
+                    element: #alloc::string::String::from(part),
+                    offset: start,
+                    source: None,
+                });
+            }
+        },
+    };
+
+    quote! {
+        /// Parses every element of a list joined by `separator`.
+        /// Errors report the element that failed and its byte offset within `s`.
+        pub fn parse_list(s: &str, separator: &str) -> Result<#alloc::vec::Vec<Self>, #list_error_name> {
+            let mut items = #alloc::vec::Vec::new();
+            if #input.is_empty() {
+                return Ok(items);
+            }
+
+            let mut offset = 0;
+            for part in s.split(separator) {
+                let start = offset;
+                offset += part.len() + separator.len();
+                #element
+
+                let value = <Self as core::str::FromStr>::from_str(part).map_err(|error| {
+                    #list_error_name {
+                        element: #alloc::string::String::from(part),
+                        offset: start,
+                        source: Some(error),
+                    }
+                })?;
+
+                #duplicate
+                items.push(value);
+            }
+
+            Ok(items)
+        }
+    }
+}
+
+/// `join`, writing every item joined by a separator.
+pub fn join(has_fields: bool, alloc: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let write = match has_fields {
+        true => quote!(core::fmt::Write::write_fmt(&mut out, format_args!("{}", item)).unwrap();),
+        false => quote!(out.push_str(item.as_str());),
+    };
+
+    quote! {
+        /// Writes every item, joined by `separator`.
+        pub fn join(items: &[Self], separator: &str) -> #alloc::string::String {
+            let mut out = #alloc::string::String::new();
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(separator);
+                }
+                #write
+            }
+
+            out
+        }
+    }
+}
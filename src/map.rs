@@ -0,0 +1,181 @@
+use quote::{format_ident, quote};
+use syn::{Ident, Visibility};
+
+pub fn map_ident(enum_name: &Ident) -> Ident {
+    format_ident!("{}Map", enum_name)
+}
+
+/// A `<Enum>Map<V>` holding one value per variant of an enum without fields, stored in declaration order.
+pub fn map_impl(
+    enum_name: &Ident,
+    vis: &Visibility,
+    count: usize,
+    serde: bool,
+) -> proc_macro2::TokenStream {
+    let map_name = map_ident(enum_name);
+    let doc = format!("A table holding one value per [`{}`] variant.", enum_name);
+
+    let serde_impl = serde.then(|| {
+        let expecting = format!("a map keyed by {}", enum_name);
+        let key_expecting = format!("a {} name", enum_name);
+        quote! {
+            impl<V: serde::Serialize> serde::Serialize for #map_name<V> {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: serde::Serializer,
+                {
+                    use serde::ser::SerializeMap;
+
+                    let mut map = serializer.serialize_map(Some(#count))?;
+                    for (key, value) in self.iter() {
+                        map.serialize_entry(key.as_str(), value)?;
+                    }
+                    map.end()
+                }
+            }
+
+            impl<'de, V: serde::Deserialize<'de>> serde::Deserialize<'de> for #map_name<V> {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    // Keys are read by name whatever the enum's own representation,
+                    // so rejected variants and `serde = "index"` enums round-trip too
+                    struct Key(usize);
+
+                    impl<'de> serde::Deserialize<'de> for Key {
+                        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                        where
+                            D: serde::Deserializer<'de>,
+                        {
+                            struct KeyVisitor;
+
+                            impl<'de> serde::de::Visitor<'de> for KeyVisitor {
+                                type Value = Key;
+
+                                fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                                    f.write_str(#key_expecting)
+                                }
+
+                                fn visit_str<E>(self, value: &str) -> Result<Key, E>
+                                where
+                                    E: serde::de::Error,
+                                {
+                                    #enum_name::NAMES
+                                        .iter()
+                                        .position(|name| *name == value)
+                                        .map(Key)
+                                        .ok_or_else(|| E::unknown_variant(value, #enum_name::NAMES))
+                                }
+
+                                fn visit_bytes<E>(self, value: &[u8]) -> Result<Key, E>
+                                where
+                                    E: serde::de::Error,
+                                {
+                                    match core::str::from_utf8(value) {
+                                        Ok(value) => self.visit_str(value),
+                                        Err(_) => Err(E::invalid_value(serde::de::Unexpected::Bytes(value), &self)),
+                                    }
+                                }
+                            }
+
+                            deserializer.deserialize_str(KeyVisitor)
+                        }
+                    }
+
+                    struct Visitor<V>(core::marker::PhantomData<V>);
+
+                    impl<'de, V: serde::Deserialize<'de>> serde::de::Visitor<'de> for Visitor<V> {
+                        type Value = #map_name<V>;
+
+                        fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                            f.write_str(#expecting)
+                        }
+
+                        fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+                        where
+                            A: serde::de::MapAccess<'de>,
+                        {
+                            let mut values: [Option<V>; #count] = core::array::from_fn(|_| None);
+                            while let Some(Key(i)) = access.next_key()? {
+                                if values[i].is_some() {
+                                    return Err(serde::de::Error::duplicate_field(#enum_name::NAMES[i]));
+                                }
+                                values[i] = Some(access.next_value()?);
+                            }
+
+                            if let Some(i) = values.iter().position(Option::is_none) {
+                                return Err(serde::de::Error::missing_field(#enum_name::NAMES[i]));
+                            }
+
+                            Ok(#map_name::from_fn(|key| values[key.index()].take().unwrap()))
+                        }
+                    }
+
+                    deserializer.deserialize_map(Visitor(core::marker::PhantomData))
+                }
+            }
+        }
+    });
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #vis struct #map_name<V>([V; #count]);
+
+        impl<V> #map_name<V> {
+            /// Builds the map by calling `f` with every variant, in declaration order.
+            pub fn from_fn(mut f: impl FnMut(#enum_name) -> V) -> Self {
+                #map_name(core::array::from_fn(|i| f(#enum_name::from_index(i).unwrap())))
+            }
+
+            pub fn get(&self, key: &#enum_name) -> &V {
+                &self.0[key.index()]
+            }
+
+            pub fn get_mut(&mut self, key: &#enum_name) -> &mut V {
+                &mut self.0[key.index()]
+            }
+
+            /// Every variant alongside its value, in declaration order.
+            pub fn iter(&self) -> impl DoubleEndedIterator<Item = (#enum_name, &V)> + ExactSizeIterator {
+                (0..#count).map(|i| #enum_name::from_index(i).unwrap()).zip(self.0.iter())
+            }
+
+            /// Every variant alongside its value, mutably, in declaration order.
+            pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = (#enum_name, &mut V)> + ExactSizeIterator {
+                (0..#count).map(|i| #enum_name::from_index(i).unwrap()).zip(self.0.iter_mut())
+            }
+
+            pub fn values(&self) -> core::slice::Iter<'_, V> {
+                self.0.iter()
+            }
+
+            pub fn values_mut(&mut self) -> core::slice::IterMut<'_, V> {
+                self.0.iter_mut()
+            }
+        }
+
+        impl<V: Default> Default for #map_name<V> {
+            fn default() -> Self {
+                #map_name::from_fn(|_| V::default())
+            }
+        }
+
+        impl<V> core::ops::Index<#enum_name> for #map_name<V> {
+            type Output = V;
+
+            fn index(&self, key: #enum_name) -> &V {
+                self.get(&key)
+            }
+        }
+
+        impl<V> core::ops::IndexMut<#enum_name> for #map_name<V> {
+            fn index_mut(&mut self, key: #enum_name) -> &mut V {
+                self.get_mut(&key)
+            }
+        }
+
+        #serde_impl
+    }
+}
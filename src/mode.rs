@@ -0,0 +1,187 @@
+use heck::AsSnakeCase;
+use quote::quote;
+
+/// How strictly an input has to match a variant's spelling.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CaseMode {
+    /// Byte for byte.
+    Exact,
+    /// Ignoring the case of every character.
+    IgnoreCase,
+    /// Ignoring case and separators, comparing the words heck splits both strings into.
+    AnyCase,
+}
+
+impl CaseMode {
+    fn words(s: &str) -> Vec<String> {
+        AsSnakeCase(s)
+            .to_string()
+            .split('_')
+            .filter(|word| !word.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// The form two spellings are compared in under this mode.
+    pub fn fold(self, s: &str) -> String {
+        match self {
+            CaseMode::Exact => s.to_string(),
+            CaseMode::IgnoreCase => s.chars().flat_map(char::to_lowercase).collect(),
+            CaseMode::AnyCase => CaseMode::words(s).join("_"),
+        }
+    }
+
+    /// Whether an input starting with `prefix` could go on to match `spelling` under this mode.
+    pub fn starts_with(self, spelling: &str, prefix: &str) -> bool {
+        match self {
+            CaseMode::Exact => spelling.starts_with(prefix),
+            CaseMode::IgnoreCase => self.fold(spelling).starts_with(&self.fold(prefix)),
+            CaseMode::AnyCase => {
+                let squash = |s: &str| {
+                    s.chars()
+                        .filter(|c| c.is_alphanumeric())
+                        .flat_map(char::to_lowercase)
+                        .collect::<String>()
+                };
+                squash(spelling).starts_with(&squash(prefix))
+            }
+        }
+    }
+
+    /// An expression checking whether `input` matches `spelling` under this mode.
+    /// `AnyCase` expects `words_eq` to be in scope.
+    pub fn compare(
+        self,
+        input: proc_macro2::TokenStream,
+        spelling: &str,
+    ) -> proc_macro2::TokenStream {
+        match self {
+            CaseMode::Exact => quote!(#input == #spelling),
+            CaseMode::IgnoreCase => {
+                let folded = self.fold(spelling);
+                quote!(#input.chars().flat_map(char::to_lowercase).eq(#folded.chars()))
+            }
+            CaseMode::AnyCase => {
+                let words = CaseMode::words(spelling);
+                quote!(words_eq(#input, &[#(#words),*]))
+            }
+        }
+    }
+}
+
+/// The options set through `#[parse(...)]`, with the variant level taking priority over the enum level.
+pub struct ParseMode {
+    pub case: CaseMode,
+    /// Whether the variant can also be parsed from its discriminant.
+    pub numeric: bool,
+    /// Whether any unambiguous prefix of the variant's spellings is accepted.
+    pub prefix: bool,
+}
+
+impl ParseMode {
+    fn apply(&mut self, attr: &syn::Attribute) -> syn::Result<()> {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("exact") {
+                self.case = CaseMode::Exact;
+            } else if meta.path.is_ident("ignore_case") {
+                self.case = CaseMode::IgnoreCase;
+            } else if meta.path.is_ident("any_case") {
+                self.case = CaseMode::AnyCase;
+            } else if meta.path.is_ident("allow_numeric") {
+                self.numeric = true;
+            } else if meta.path.is_ident("prefix") {
+                self.prefix = true;
+            } else {
+                return Err(meta.error(
+                    "expected `exact`, `ignore_case`, `any_case`, `allow_numeric` or `prefix`",
+                ));
+            }
+
+            Ok(())
+        })
+    }
+
+    pub fn resolve(
+        input_attrs: &[syn::Attribute],
+        variant_attrs: &[syn::Attribute],
+    ) -> syn::Result<Self> {
+        let mut mode = ParseMode {
+            case: CaseMode::Exact,
+            numeric: false,
+            prefix: false,
+        };
+
+        for attr in input_attrs
+            .iter()
+            .chain(variant_attrs.iter())
+            .filter(|attr| attr.path().is_ident("parse"))
+        {
+            mode.apply(attr)?;
+        }
+
+        Ok(mode)
+    }
+
+    /// Checks a `#[parse(...)]` attribute on its own.
+    pub fn check(attr: &syn::Attribute) -> syn::Result<()> {
+        ParseMode::resolve(std::slice::from_ref(attr), &[]).map(|_| ())
+    }
+}
+
+/// A local function comparing an input against a variant's words without allocating,
+/// splitting the input the same way heck does.
+pub fn words_eq() -> proc_macro2::TokenStream {
+    quote! {
+        fn words_eq(input: &str, words: &[&str]) -> bool {
+            let mut words = words.iter();
+            let mut word_eq = |word: &str| match words.next() {
+                Some(expected) => word.chars().flat_map(char::to_lowercase).eq(expected.chars()),
+                None => false,
+            };
+
+            for part in input.split(|c: char| !c.is_alphanumeric()) {
+                let mut chars = part.char_indices().peekable();
+                let mut init = 0;
+                let mut lowercase = false;
+                let mut uppercase = false;
+
+                while let Some((i, c)) = chars.next() {
+                    match chars.peek() {
+                        Some(&(next_i, next)) => {
+                            let (next_lowercase, next_uppercase) = if c.is_lowercase() {
+                                (true, false)
+                            } else if c.is_uppercase() {
+                                (false, true)
+                            } else {
+                                (lowercase, uppercase)
+                            };
+
+                            if next_lowercase && next.is_uppercase() {
+                                if !word_eq(&part[init..next_i]) {
+                                    return false;
+                                }
+                                init = next_i;
+                                (lowercase, uppercase) = (false, false);
+                            } else if uppercase && c.is_uppercase() && next.is_lowercase() {
+                                if !word_eq(&part[init..i]) {
+                                    return false;
+                                }
+                                init = i;
+                                (lowercase, uppercase) = (false, false);
+                            } else {
+                                (lowercase, uppercase) = (next_lowercase, next_uppercase);
+                            }
+                        }
+                        None => {
+                            if !word_eq(&part[init..]) {
+                                return false;
+                            }
+                        }
+                    }
+                }
+            }
+
+            words.next().is_none()
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use crate::validate::Errors;
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{spanned::Spanned, Attribute, Data, DeriveInput, Meta};
+
+/// Options of `#[to_and_fro(...)]` that are also accepted as their own, now deprecated, attributes.
+const HELPERS: [&str; 10] = [
+    "input_case",
+    "output_case",
+    "casing",
+    "default",
+    "serde",
+    "reject",
+    "rename",
+    "alias",
+    "parse",
+    "payload",
+];
+
+/// Options of `#[to_and_fro(...)]` that are read like the helpers, but were never attributes of their own.
+const NAMESPACED: [&str; 1] = ["description"];
+
+/// Enum level options of `#[to_and_fro(...)]`, read as a whole by `Options::parse`.
+const OPTIONS: [&str; 9] = [
+    "skip", "impls", "bound", "set", "map", "list", "clap", "no_std", "alloc",
+];
+
+/// Whether an attribute is one of this crate's bare helpers, rather than the std or serde attribute of the same name.
+fn is_legacy(attr: &Attribute) -> bool {
+    let path = attr.path();
+    match &attr.meta {
+        Meta::Path(_) if path.is_ident("default") => false,
+        Meta::List(_) if path.is_ident("serde") => false,
+        _ => HELPERS.iter().any(|helper| path.is_ident(helper)),
+    }
+}
+
+/// A warning pointing at a bare helper attribute, emitted through a use of a deprecated item.
+fn deprecation(attr: &Attribute) -> TokenStream {
+    let path = attr.path();
+    let replacement = match &attr.meta {
+        Meta::Path(_) => path.to_token_stream().to_string(),
+        _ => format!("{}(...)", path.to_token_stream()),
+    };
+    let note = format!(
+        "#[{}] is deprecated, use #[to_and_fro({})] instead",
+        path.to_token_stream(),
+        replacement
+    );
+
+    quote_spanned! {path.span()=>
+        const _: () = {
+            #[deprecated(note = #note)]
+            #[allow(non_camel_case_types)]
+            struct to_and_fro_helper_attribute;
+            let _ = to_and_fro_helper_attribute;
+        };
+    }
+}
+
+/// Splits a `#[to_and_fro(...)]` attribute into the helper attribute each option stands for.
+/// `key = "value"` becomes `#[key("value")]`, `key(...)` becomes `#[key(...)]` and `key` becomes `#[key]`.
+fn desugar(attr: &Attribute) -> syn::Result<Vec<Attribute>> {
+    let mut attrs = Vec::new();
+    attr.parse_nested_meta(|meta| {
+        let path = &meta.path;
+        let known = HELPERS.iter().chain(&NAMESPACED).chain(&OPTIONS);
+        if !known.clone().any(|helper| path.is_ident(helper)) {
+            return Err(meta.error(format!(
+                "unknown option, expected one of: {}",
+                known.copied().collect::<Vec<_>>().join(", ")
+            )));
+        }
+
+        let parenthesized = meta.input.peek(syn::token::Paren);
+        let args = if meta.input.peek(syn::Token![=]) {
+            let value = meta.value()?.parse::<syn::Lit>()?;
+            Some(quote!(#value))
+        } else if parenthesized {
+            let content;
+            syn::parenthesized!(content in meta.input);
+            Some(content.parse::<TokenStream>()?)
+        } else {
+            None
+        };
+
+        let is_option = OPTIONS.iter().any(|option| path.is_ident(option));
+        attrs.push(match (is_option, args) {
+            (true, Some(args)) if path.is_ident("bound") => {
+                syn::parse_quote_spanned!(path.span()=> #[to_and_fro(#path = #args)])
+            }
+            (true, Some(args)) => {
+                syn::parse_quote_spanned!(path.span()=> #[to_and_fro(#path(#args))])
+            }
+            (true, None) => syn::parse_quote_spanned!(path.span()=> #[to_and_fro(#path)]),
+            // A bare `#[serde(...)]` is serde_derive's, so `serde(tag = "...")` stays namespaced.
+            (false, Some(args)) if path.is_ident("serde") && parenthesized => {
+                syn::parse_quote_spanned!(path.span()=> #[to_and_fro(#path(#args))])
+            }
+            (false, Some(args)) => syn::parse_quote_spanned!(path.span()=> #[#path(#args)]),
+            (false, None) => syn::parse_quote_spanned!(path.span()=> #[#path]),
+        });
+
+        Ok(())
+    })?;
+
+    Ok(attrs)
+}
+
+fn normalize_attrs(
+    attrs: &mut Vec<Attribute>,
+    warnings: &mut Vec<TokenStream>,
+    errors: &mut Errors,
+) {
+    let mut normalized = Vec::new();
+    for attr in attrs.drain(..) {
+        if attr.path().is_ident("to_and_fro") {
+            normalized.extend(errors.check(desugar(&attr)).into_iter().flatten());
+        } else {
+            if is_legacy(&attr) {
+                warnings.push(deprecation(&attr));
+            }
+            normalized.push(attr);
+        }
+    }
+
+    *attrs = normalized;
+}
+
+/// Rewrites `#[to_and_fro(...)]` on the enum and its variants into the helper attributes it stands for,
+/// returning a deprecation warning for every bare helper attribute used directly.
+pub fn normalize(input: &mut DeriveInput) -> syn::Result<Vec<TokenStream>> {
+    let mut warnings = Vec::new();
+    let mut errors = Errors::default();
+
+    normalize_attrs(&mut input.attrs, &mut warnings, &mut errors);
+    if let Data::Enum(data) = &mut input.data {
+        for variant in data.variants.iter_mut() {
+            normalize_attrs(&mut variant.attrs, &mut warnings, &mut errors);
+        }
+    }
+
+    errors.finish().map(|_| warnings)
+}
@@ -0,0 +1,207 @@
+use crate::tagging::Tagging;
+use quote::quote;
+use syn::{meta::ParseNestedMeta, punctuated::Punctuated, Token, WherePredicate};
+
+/// The traits the derive implements alongside `Display` and `FromStr`, which can be opted out of.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Derive {
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Debug,
+}
+
+impl Derive {
+    fn parse(meta: &ParseNestedMeta) -> syn::Result<Self> {
+        let derive = match meta.path.get_ident().map(|ident| ident.to_string()) {
+            Some(ident) if ident == "Clone" => Derive::Clone,
+            Some(ident) if ident == "Copy" => Derive::Copy,
+            Some(ident) if ident == "PartialEq" => Derive::PartialEq,
+            Some(ident) if ident == "Eq" => Derive::Eq,
+            Some(ident) if ident == "Hash" => Derive::Hash,
+            Some(ident) if ident == "Debug" => Derive::Debug,
+            _ => {
+                return Err(meta.error(
+                    "expected one of `Clone`, `Copy`, `PartialEq`, `Eq`, `Hash` or `Debug`",
+                ))
+            }
+        };
+
+        Ok(derive)
+    }
+
+    fn parse_list(meta: &ParseNestedMeta) -> syn::Result<Vec<Self>> {
+        let mut derives = Vec::new();
+        meta.parse_nested_meta(|meta| {
+            derives.push(Derive::parse(&meta)?);
+            Ok(())
+        })?;
+
+        Ok(derives)
+    }
+}
+
+/// What `parse_list` does with a variant appearing more than once.
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum Duplicates {
+    #[default]
+    Allow,
+    Ignore,
+    Reject,
+}
+
+/// The behaviour of `parse_list`, set through `list(...)`.
+pub struct List {
+    pub trim: bool,
+    pub duplicates: Duplicates,
+}
+
+impl Default for List {
+    fn default() -> Self {
+        List {
+            trim: true,
+            duplicates: Duplicates::Allow,
+        }
+    }
+}
+
+impl List {
+    fn parse(&mut self, meta: &ParseNestedMeta) -> syn::Result<()> {
+        meta.parse_nested_meta(|meta| {
+            if meta.path.is_ident("trim") {
+                self.trim = meta.value()?.parse::<syn::LitBool>()?.value;
+            } else if meta.path.is_ident("duplicates") {
+                let lit = meta.value()?.parse::<syn::LitStr>()?;
+                self.duplicates = match lit.value().as_str() {
+                    "allow" => Duplicates::Allow,
+                    "ignore" => Duplicates::Ignore,
+                    "reject" => Duplicates::Reject,
+                    _ => {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "expected \"allow\", \"ignore\" or \"reject\"",
+                        ))
+                    }
+                };
+            } else {
+                return Err(meta.error("expected `trim = ...` or `duplicates = \"...\"`"));
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// Enum level options set through `#[to_and_fro(...)]`.
+#[derive(Default)]
+pub struct Options {
+    skip: Vec<Derive>,
+    impls: Option<Vec<Derive>>,
+    pub bound: Option<Vec<WherePredicate>>,
+    /// The separator used by the generated set type, when `set` is given.
+    pub set: Option<String>,
+    /// Whether to generate a map type keyed by the enum.
+    pub map: bool,
+    pub list: List,
+    /// Whether to implement `clap::ValueEnum`, which needs the `clap` feature.
+    pub clap: bool,
+    /// Whether the generated code has to build without `std`.
+    pub no_std: bool,
+    /// Whether the `alloc` crate can be used under `no_std`.
+    pub alloc: bool,
+    /// The tag, and content, fields of an internally or adjacently tagged `serde` representation.
+    pub tagging: Option<Tagging>,
+}
+
+impl Options {
+    pub fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut options = Options::default();
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("to_and_fro"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    options.skip.extend(Derive::parse_list(&meta)?);
+                } else if meta.path.is_ident("impls") {
+                    options
+                        .impls
+                        .get_or_insert_with(Vec::new)
+                        .extend(Derive::parse_list(&meta)?);
+                } else if meta.path.is_ident("bound") {
+                    let bound = meta
+                        .value()?
+                        .parse::<syn::LitStr>()?
+                        .parse_with(Punctuated::<WherePredicate, Token![,]>::parse_terminated)?;
+                    options.bound.get_or_insert_with(Vec::new).extend(bound);
+                } else if meta.path.is_ident("set") {
+                    let mut separator = "|".to_string();
+                    if meta.input.peek(syn::token::Paren) {
+                        meta.parse_nested_meta(|meta| {
+                            if !meta.path.is_ident("separator") {
+                                return Err(meta.error("expected `separator = \"...\"`"));
+                            }
+
+                            separator = meta.value()?.parse::<syn::LitStr>()?.value();
+                            Ok(())
+                        })?;
+                    }
+
+                    options.set = Some(separator);
+                } else if meta.path.is_ident("map") {
+                    options.map = true;
+                } else if meta.path.is_ident("list") {
+                    options.list.parse(&meta)?;
+                } else if meta.path.is_ident("clap") {
+                    options.clap = true;
+                } else if meta.path.is_ident("no_std") {
+                    options.no_std = true;
+                } else if meta.path.is_ident("alloc") {
+                    options.alloc = true;
+                } else if meta.path.is_ident("serde") {
+                    options.tagging = Some(Tagging::parse(&meta)?);
+                } else {
+                    return Err(meta.error(
+                        "expected `skip(...)`, `impls(...)`, `bound = \"...\"`, `set`, `map`, `list(...)`, `clap`, `no_std` or `alloc`",
+                    ));
+                }
+
+                if !options.skip.is_empty() && options.impls.is_some() {
+                    return Err(meta.error("`skip(...)` and `impls(...)` can't be used together"));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(options)
+    }
+
+    /// The crate owned strings and collections come from, or `None` if only `core` is available.
+    pub fn alloc_crate(&self) -> Option<proc_macro2::TokenStream> {
+        match (self.no_std, self.alloc) {
+            (false, _) => Some(quote!(std)),
+            (true, true) => Some(quote!(alloc)),
+            (true, false) => None,
+        }
+    }
+
+    /// The `Error` trait to implement, which is only available from `core` since Rust 1.81.
+    pub fn error_trait(&self) -> proc_macro2::TokenStream {
+        match self.no_std {
+            true => quote!(core::error::Error),
+            false => quote!(std::error::Error),
+        }
+    }
+
+    /// Whether `derive` should be implemented. Unless asked for through `impls(...)`, `Copy` is only
+    /// implemented for enums without fields.
+    pub fn implements(&self, derive: Derive, fieldless: bool) -> bool {
+        match &self.impls {
+            Some(impls) => impls.contains(&derive),
+            None => !self.skip.contains(&derive) && (derive != Derive::Copy || fieldless),
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use crate::mode::CaseMode;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{spanned::Spanned, Field, Fields, Ident, LitStr, Member, Type, Variant};
+
+/// Delimiters used to write and read the fields of tuple and struct variants.
+pub struct Syntax {
+    open: String,
+    close: String,
+    separator: String,
+    assign: String,
+}
+
+impl Syntax {
+    fn for_fields(fields: &Fields) -> Self {
+        let (open, close) = match fields {
+            Fields::Named(_) => ("{", "}"),
+            _ => ("(", ")"),
+        };
+
+        Syntax {
+            open: open.to_string(),
+            close: close.to_string(),
+            separator: ",".to_string(),
+            assign: "=".to_string(),
+        }
+    }
+
+    fn apply(&mut self, attr: &syn::Attribute) -> syn::Result<()> {
+        attr.parse_nested_meta(|meta| {
+            let value = meta.value()?.parse::<LitStr>()?.value();
+            if meta.path.is_ident("open") {
+                self.open = value;
+            } else if meta.path.is_ident("close") {
+                self.close = value;
+            } else if meta.path.is_ident("separator") {
+                self.separator = value;
+            } else if meta.path.is_ident("assign") {
+                self.assign = value;
+            } else {
+                return Err(meta.error("expected `open`, `close`, `separator` or `assign`"));
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Resolves the syntax for a variant, with variant level `#[payload(...)]` taking priority over the enum level one.
+    pub fn resolve(input_attrs: &[syn::Attribute], variant: &Variant) -> syn::Result<Self> {
+        let mut syntax = Syntax::for_fields(&variant.fields);
+        for attr in input_attrs
+            .iter()
+            .chain(variant.attrs.iter())
+            .filter(|attr| attr.path().is_ident("payload"))
+        {
+            syntax.apply(attr)?;
+        }
+
+        Ok(syntax)
+    }
+}
+
+/// Whether a field is a `PhantomData` marker, which is left out of the written and parsed payload.
+pub fn is_phantom(ty: &Type) -> bool {
+    match ty {
+        Type::Path(ty) => ty
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "PhantomData"),
+        _ => false,
+    }
+}
+
+/// The fields making up a variant's written and parsed payload.
+pub fn payload_fields(variant: &Variant) -> impl Iterator<Item = &Field> {
+    variant.fields.iter().filter(|field| !is_phantom(&field.ty))
+}
+
+pub fn has_fields(variant: &Variant) -> bool {
+    payload_fields(variant).next().is_some()
+}
+
+fn members(variant: &Variant) -> Vec<Member> {
+    variant.fields.members().collect()
+}
+
+pub fn bindings(variant: &Variant, prefix: &str) -> Vec<Ident> {
+    (0..variant.fields.len())
+        .map(|i| format_ident!("__{}{}", prefix, i))
+        .collect()
+}
+
+/// A pattern matching the variant while ignoring its fields.
+pub fn wildcard(enum_name: &Ident, variant: &Variant) -> proc_macro2::TokenStream {
+    let variant_name = &variant.ident;
+    match variant.fields {
+        Fields::Unit => quote!(#enum_name::#variant_name),
+        _ => quote!(#enum_name::#variant_name { .. }),
+    }
+}
+
+/// A pattern matching the variant, binding each field to `__<prefix><index>`.
+pub fn pattern(enum_name: &Ident, variant: &Variant, prefix: &str) -> proc_macro2::TokenStream {
+    let variant_name = &variant.ident;
+    match variant.fields {
+        Fields::Unit => quote!(#enum_name::#variant_name),
+        _ => {
+            let members = members(variant);
+            let bindings = bindings(variant, prefix);
+            quote!(#enum_name::#variant_name { #(#members: #bindings),* })
+        }
+    }
+}
+
+/// An expression constructing the variant, with each field produced by `value`.
+pub fn construct(
+    enum_name: &Ident,
+    variant: &Variant,
+    mut value: impl FnMut(&Ident) -> proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let variant_name = &variant.ident;
+    match variant.fields {
+        Fields::Unit => quote!(#enum_name::#variant_name),
+        _ => {
+            let members = members(variant);
+            let values = bindings(variant, "f").into_iter().map(|b| value(&b));
+            quote!(#enum_name::#variant_name { #(#members: #values),* })
+        }
+    }
+}
+
+/// Statements writing the payload of a variant (bound with the `f` prefix) to the formatter `f`.
+pub fn display(
+    input_attrs: &[syn::Attribute],
+    variant: &Variant,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let syntax = Syntax::resolve(input_attrs, variant)?;
+    let Syntax {
+        open,
+        close,
+        separator,
+        assign,
+    } = &syntax;
+
+    let fields = variant
+        .fields
+        .iter()
+        .zip(bindings(variant, "f"))
+        .filter(|(field, _)| !is_phantom(&field.ty))
+        .enumerate()
+        .map(|(i, (field, binding))| {
+            let separator = (i > 0).then(|| quote!(f.write_str(#separator)?;));
+            let key = field.ident.as_ref().map(|ident| {
+                let key = ident.to_string();
+                quote! {
+                    f.write_str(#key)?;
+                    f.write_str(#assign)?;
+                }
+            });
+
+            quote! {
+                #separator
+                #key
+                core::fmt::Display::fmt(#binding, f)?;
+            }
+        });
+
+    Ok(quote! {
+        f.write_str(#open)?;
+        #(#fields)*
+        f.write_str(#close)
+    })
+}
+
+/// A block attempting to parse `s` as a variant tagged with `cased_name`, returning early on success.
+/// When `report` is set, a field that doesn't parse once the tag and delimiters matched is stored in
+/// `failed`, for the error to name.
+pub fn parse(
+    enum_name: &Ident,
+    input_attrs: &[syn::Attribute],
+    variant: &Variant,
+    cased_name: &str,
+    case: CaseMode,
+    report: bool,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let syntax = Syntax::resolve(input_attrs, variant)?;
+    let Syntax {
+        open,
+        close,
+        separator,
+        assign,
+    } = &syntax;
+
+    let count = payload_fields(variant).count();
+    let fields = variant
+        .fields
+        .iter()
+        .zip(members(variant))
+        .zip(bindings(variant, "f"))
+        .map(|((field, member), binding)| {
+            let ty = &field.ty;
+            if is_phantom(ty) {
+                return quote!(let #binding = core::marker::PhantomData;);
+            }
+
+            let label = match &member {
+                Member::Named(ident) => ident.to_string(),
+                Member::Unnamed(index) => index.index.to_string(),
+            };
+            let value = match member {
+                Member::Named(_) => quote! {
+                    fields
+                        .next()
+                        .and_then(|field| field.split_once(#assign))
+                        .filter(|(key, _)| *key == #label)
+                        .map(|(_, value)| value)
+                },
+                Member::Unnamed(_) => quote!(fields.next()),
+            };
+
+            quote! {
+                let #binding = #value
+                    .and_then(|value| <#ty as core::str::FromStr>::from_str(value).ok())
+                    .ok_or(#label)?;
+            }
+        });
+
+    let value = construct(enum_name, variant, |binding| quote!(#binding));
+    let payload = match case {
+        CaseMode::Exact => quote! {
+            s.strip_prefix(#cased_name).and_then(|s| s.strip_prefix(#open))
+        },
+        _ => {
+            let matches = case.compare(quote!(tag), cased_name);
+            quote! {
+                s.split_once(#open).filter(|&(tag, _)| #matches).map(|(_, s)| s)
+            }
+        }
+    };
+    let failure = match report {
+        true => quote!(failed = Some(field)),
+        false => quote!({}),
+    };
+
+    Ok(quote! {
+        if let Some(payload) = #payload.and_then(|s| s.strip_suffix(#close)) {
+            let parsed = (|| -> Result<Self, &'static str> {
+                let mut fields = payload.splitn(#count, #separator);
+                #(#fields)*
+                Ok(#value)
+            })();
+
+            match parsed {
+                Ok(value) => return Ok(value),
+                Err(field) => #failure,
+            }
+        }
+    })
+}
+
+/// Compile time checks that every field can be written with `Display`, and parsed with `FromStr` unless the variant is rejected.
+pub fn assert_round_trip(variant: &Variant, parsed: bool) -> proc_macro2::TokenStream {
+    let asserts = payload_fields(variant).map(|field| {
+        let ty = &field.ty;
+        if parsed {
+            quote_spanned!(ty.span()=> assert_from_str::<#ty>(); assert_display::<#ty>();)
+        } else {
+            quote_spanned!(ty.span()=> assert_display::<#ty>();)
+        }
+    });
+
+    quote!(#(#asserts)*)
+}
+
+/// Checks a `#[payload(...)]` attribute without resolving it against a variant.
+pub fn check(attr: &syn::Attribute) -> syn::Result<()> {
+    Syntax::for_fields(&Fields::Unit).apply(attr)
+}
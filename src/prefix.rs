@@ -0,0 +1,79 @@
+use crate::{error::ambiguous_value, mode::CaseMode};
+use proc_macro2::Span;
+use quote::quote;
+use std::collections::BTreeMap;
+use syn::Ident;
+
+/// Every proper prefix of the `prefixed` spellings that isn't itself an accepted spelling, shortest
+/// first, with the indexes of every `accepted` spelling it starts under that spelling's case mode.
+pub fn prefixes<'a>(
+    prefixed: impl IntoIterator<Item = &'a str>,
+    accepted: &[(&str, CaseMode)],
+) -> BTreeMap<&'a str, Vec<usize>> {
+    let mut prefixes = BTreeMap::<&str, Vec<usize>>::new();
+    for spelling in prefixed {
+        for (end, _) in spelling.char_indices().skip(1) {
+            let prefix = &spelling[..end];
+            if prefixes.contains_key(prefix) || accepted.iter().any(|(other, _)| *other == prefix) {
+                continue;
+            }
+
+            let matches = accepted
+                .iter()
+                .enumerate()
+                .filter(|(_, (other, case))| case.starts_with(other, prefix))
+                .map(|(i, _)| i)
+                .collect();
+            prefixes.insert(prefix, matches);
+        }
+    }
+
+    prefixes
+}
+
+/// Arms accepting every prefix of the given spellings, shortest unique prefix first.
+/// A prefix starting spellings of several variants, whether or not they take prefixes themselves,
+/// returns an error listing the spellings it could stand for. Prefixes that are themselves an
+/// accepted spelling are left to the exact arms.
+pub fn prefix_arms(
+    enum_name: &Ident,
+    prefixed: &[(String, Ident, proc_macro2::TokenStream)],
+    accepted: &[(String, Span, Ident, CaseMode)],
+) -> Vec<(Vec<String>, proc_macro2::TokenStream)> {
+    let spellings = accepted
+        .iter()
+        .map(|(spelling, _, _, case)| (spelling.as_str(), *case))
+        .collect::<Vec<_>>();
+    let prefixes = prefixes(
+        prefixed.iter().map(|(spelling, _, _)| spelling.as_str()),
+        &spellings,
+    );
+
+    let mut unique = BTreeMap::<&Ident, Vec<&str>>::new();
+    let mut arms = Vec::new();
+    for (prefix, matches) in prefixes {
+        let (_, _, variant, _) = &accepted[matches[0]];
+        if matches.iter().all(|&i| accepted[i].2 == *variant) {
+            unique.entry(variant).or_default().push(prefix);
+            continue;
+        }
+
+        let candidates = matches
+            .iter()
+            .map(|&i| accepted[i].0.clone())
+            .collect::<Vec<_>>();
+        let error = ambiguous_value(enum_name, &candidates);
+        arms.push((vec![prefix.to_string()], quote!(Err(#error))));
+    }
+
+    for (variant, prefixes) in unique {
+        let (_, _, value) = prefixed
+            .iter()
+            .find(|(_, other, _)| other == variant)
+            .expect("a prefix only matching one variant comes from its spelling");
+        let prefixes = prefixes.into_iter().map(String::from).collect();
+        arms.push((prefixes, quote!(Ok(#value))));
+    }
+
+    arms
+}
@@ -0,0 +1,43 @@
+use proc_macro2::Span;
+use syn::{punctuated::Punctuated, LitStr, Token, Variant};
+
+/// The exact string set through `#[rename("...")]`, used for both parsing and display.
+pub fn get_rename(attrs: &[syn::Attribute]) -> syn::Result<Option<LitStr>> {
+    attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("rename"))
+        .map(|attr| attr.parse_args::<LitStr>())
+        .transpose()
+}
+
+/// The extra strings accepted when parsing, set through `#[alias("...", ...)]`.
+pub fn get_aliases(attrs: &[syn::Attribute]) -> syn::Result<Vec<LitStr>> {
+    let mut aliases = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("alias")) {
+        aliases.extend(attr.parse_args_with(Punctuated::<LitStr, Token![,]>::parse_terminated)?);
+    }
+
+    Ok(aliases)
+}
+
+/// Every string a variant accepts when parsing, paired with the span to blame should it collide with another variant.
+pub fn spellings(variant: &Variant, cased_names: Vec<String>) -> syn::Result<Vec<(String, Span)>> {
+    let span = get_rename(&variant.attrs)?
+        .map(|lit| lit.span())
+        .unwrap_or_else(|| variant.ident.span());
+
+    let mut spellings = cased_names
+        .into_iter()
+        .map(|cased_name| (cased_name, span))
+        .collect::<Vec<_>>();
+    for alias in get_aliases(&variant.attrs)? {
+        if spellings
+            .iter()
+            .all(|(spelling, _)| *spelling != alias.value())
+        {
+            spellings.push((alias.value(), alias.span()));
+        }
+    }
+
+    Ok(spellings)
+}
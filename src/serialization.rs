@@ -0,0 +1,298 @@
+use crate::{error::error_ident, options::Options, tagging::Tagging};
+use quote::{format_ident, quote};
+use syn::{Ident, LitStr, Meta};
+
+/// How `serde` writes a variant, set through `#[to_and_fro(serde = "...")]` or `#[to_and_fro(serde(...))]`.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Representation {
+    /// The `Display` spelling, the default for a bare `serde`.
+    String,
+    /// The discriminant, as the `#[repr]` type.
+    Index,
+    /// `serialize_unit_variant`, tagged natively by formats that have enums.
+    UnitVariant,
+    /// The spelling for human readable formats, the discriminant otherwise.
+    Auto,
+    /// The variant tagged with its spelling, alongside its fields.
+    Tagged(Tagging),
+}
+
+impl Representation {
+    /// Reads `#[serde]`, or `#[serde("...")]` as `serde = "..."` desugars to. A `#[serde(...)]` holding
+    /// anything but a string is serde's own attribute, and left alone.
+    pub fn parse(attrs: &[syn::Attribute]) -> syn::Result<Option<Self>> {
+        let mut representation = None;
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            let lit = match &attr.meta {
+                Meta::Path(_) => None,
+                Meta::List(_) => match attr.parse_args::<LitStr>() {
+                    Ok(lit) => Some(lit),
+                    Err(_) => continue,
+                },
+                Meta::NameValue(_) => continue,
+            };
+
+            representation = Some(match lit {
+                None => Representation::String,
+                Some(lit) => match lit.value().as_str() {
+                    "string" => Representation::String,
+                    "index" => Representation::Index,
+                    "unit_variant" => Representation::UnitVariant,
+                    "auto" => Representation::Auto,
+                    "external" => Representation::Tagged(Tagging::External),
+                    _ => {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "expected \"string\", \"index\", \"unit_variant\", \"auto\" or \"external\"",
+                        ))
+                    }
+                },
+            });
+        }
+
+        Ok(representation)
+    }
+
+    /// The representation in use, with `serde(tag = "...")` taking priority over a bare `serde`.
+    pub fn resolve(attrs: &[syn::Attribute], options: &Options) -> syn::Result<Option<Self>> {
+        Ok(match &options.tagging {
+            Some(tagging) => Some(Representation::Tagged(tagging.clone())),
+            None => Representation::parse(attrs)?,
+        })
+    }
+}
+
+/// The integer type a `#[repr]` is written as, since serde has no `usize` or `isize`.
+fn wire_type(repr: &Ident) -> Ident {
+    match repr.to_string().as_str() {
+        "usize" => format_ident!("u64"),
+        "isize" => format_ident!("i64"),
+        _ => repr.clone(),
+    }
+}
+
+/// The `Serializer` method writing a `#[repr]` type.
+fn integer_method(prefix: &str, repr: &Ident) -> Ident {
+    format_ident!("{}_{}", prefix, wire_type(repr))
+}
+
+/// The body of `Serialize::serialize`. Only enums without fields use anything but the `Display` spelling.
+pub fn serialize(
+    enum_name: &Ident,
+    variants: &[proc_macro2::TokenStream],
+    repr: &Ident,
+    representation: &Representation,
+    fieldless: bool,
+) -> proc_macro2::TokenStream {
+    let string = match fieldless {
+        true => quote!(serializer.serialize_str(self.as_str())),
+        false => quote!(serializer.collect_str(self)),
+    };
+    let method = integer_method("serialize", repr);
+    let wire = wire_type(repr);
+    let index = quote! {
+        serializer.#method(match self {
+            #(#enum_name::#variants => #enum_name::#variants as #wire,)*
+        })
+    };
+
+    match representation {
+        Representation::String => string,
+        Representation::Index => index,
+        Representation::UnitVariant => quote! {
+            serializer.serialize_unit_variant(stringify!(#enum_name), self.index() as u32, self.as_str())
+        },
+        Representation::Auto => quote! {
+            match serializer.is_human_readable() {
+                true => #string,
+                false => #index,
+            }
+        },
+        Representation::Tagged(_) => {
+            unreachable!("tagged enums are expanded by `tagging::serde_impl`")
+        }
+    }
+}
+
+/// The body of `Deserialize::deserialize`, parsing borrowed or owned strings and UTF-8 bytes without
+/// allocating. Enums without fields are also read from an integer where the representation writes one:
+/// the variant's position for `unit_variant`, its discriminant for `index` and `auto`. The `string`
+/// representation only reads the discriminants of `parse(allow_numeric)` variants. `readable` holds the
+/// position and name of every variant that isn't rejected, and `numeric` the ones opted in.
+pub fn deserialize(
+    enum_name: &Ident,
+    readable: &[(usize, proc_macro2::TokenStream)],
+    numeric: &[proc_macro2::TokenStream],
+    repr: &Ident,
+    representation: &Representation,
+    fieldless: bool,
+) -> proc_macro2::TokenStream {
+    let error_name = error_ident(enum_name);
+    let expecting = format!("a {} variant", enum_name);
+
+    let discriminant = |variants: Vec<&proc_macro2::TokenStream>| {
+        quote! {
+            |v| <#repr as core::convert::TryFrom<i128>>::try_from(v)
+                .ok()
+                .and_then(|v| {
+                    #(if v == #enum_name::#variants as #repr {
+                        return Some(#enum_name::#variants);
+                    })*
+                    None
+                })
+        }
+    };
+    let integer = match representation {
+        _ if !fieldless => None,
+        Representation::UnitVariant => {
+            let indices = readable.iter().map(|(i, _)| *i as i128);
+            let variants = readable.iter().map(|(_, variant)| variant);
+            Some(quote! {
+                |v| match v {
+                    #(#indices => Some(#enum_name::#variants),)*
+                    _ => None,
+                }
+            })
+        }
+        Representation::Index | Representation::Auto => Some(discriminant(
+            readable.iter().map(|(_, variant)| variant).collect(),
+        )),
+        _ if numeric.is_empty() => None,
+        _ => Some(discriminant(numeric.iter().collect())),
+    };
+
+    let (visitor, visit_ints, integer) = if let Some(integer) = integer {
+        let wide = matches!(repr.to_string().as_str(), "u128" | "i128").then(|| {
+            quote! {
+                fn visit_u128<E: serde::de::Error>(self, v: u128) -> Result<T, E> {
+                    <i128 as core::convert::TryFrom<u128>>::try_from(v)
+                        .ok()
+                        .and_then(self.0)
+                        .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Other("integer"), &self))
+                }
+
+                fn visit_i128<E: serde::de::Error>(self, v: i128) -> Result<T, E> {
+                    (self.0)(v)
+                        .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Other("integer"), &self))
+                }
+            }
+        });
+
+        (
+            quote!(
+                struct Visitor<T>(fn(i128) -> Option<T>);
+            ),
+            quote! {
+                fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<T, E> {
+                    (self.0)(v.into())
+                        .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Unsigned(v), &self))
+                }
+
+                fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<T, E> {
+                    (self.0)(v.into())
+                        .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Signed(v), &self))
+                }
+
+                #wide
+            },
+            integer,
+        )
+    } else {
+        (
+            quote!(
+                struct Visitor<T>(core::marker::PhantomData<T>);
+            ),
+            quote!(),
+            quote!(core::marker::PhantomData),
+        )
+    };
+
+    let unit_variant = (*representation == Representation::UnitVariant).then(|| {
+        quote! {
+            fn visit_enum<A: serde::de::EnumAccess<'de>>(self, data: A) -> Result<T, A::Error> {
+                let (value, variant) = data.variant_seed(self)?;
+                serde::de::VariantAccess::unit_variant(variant)?;
+                Ok(value)
+            }
+        }
+    });
+    let seed = unit_variant.is_some().then(|| {
+        quote! {
+            impl<'de, T> serde::de::DeserializeSeed<'de> for Visitor<T>
+            where
+                T: core::str::FromStr<Err = #error_name>,
+            {
+                type Value = T;
+
+                fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<T, D::Error> {
+                    deserializer.deserialize_identifier(self)
+                }
+            }
+        }
+    });
+
+    let method = integer_method("deserialize", repr);
+    let call = match (representation, fieldless) {
+        (Representation::UnitVariant, _) => quote! {
+            deserializer.deserialize_enum(stringify!(#enum_name), Self::NAMES, visitor)
+        },
+        (Representation::Index, _) => quote!(deserializer.#method(visitor)),
+        (Representation::Auto, _) => quote! {
+            match deserializer.is_human_readable() {
+                true => deserializer.deserialize_any(visitor),
+                false => deserializer.#method(visitor),
+            }
+        },
+        (Representation::String, true) => quote! {
+            match deserializer.is_human_readable() {
+                true => deserializer.deserialize_any(visitor),
+                false => deserializer.deserialize_str(visitor),
+            }
+        },
+        (Representation::String, false) => quote!(deserializer.deserialize_str(visitor)),
+        (Representation::Tagged(_), _) => {
+            unreachable!("tagged enums are expanded by `tagging::serde_impl`")
+        }
+    };
+
+    quote! {
+        #visitor
+
+        impl<'de, T> serde::de::Visitor<'de> for Visitor<T>
+        where
+            T: core::str::FromStr<Err = #error_name>,
+        {
+            type Value = T;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.write_str(#expecting)
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<T, E> {
+                v.parse().map_err(|error: #error_name| match error.candidates() {
+                    [] => E::unknown_variant(v, error.expected()),
+                    _ => E::custom(error),
+                })
+            }
+
+            fn visit_borrowed_str<E: serde::de::Error>(self, v: &'de str) -> Result<T, E> {
+                self.visit_str(v)
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<T, E> {
+                match core::str::from_utf8(v) {
+                    Ok(v) => self.visit_str(v),
+                    Err(_) => Err(E::invalid_value(serde::de::Unexpected::Bytes(v), &self)),
+                }
+            }
+
+            #visit_ints
+            #unit_variant
+        }
+
+        #seed
+
+        let visitor = Visitor::<Self>(#integer);
+        #call
+    }
+}
@@ -0,0 +1,152 @@
+use crate::error::error_ident;
+use quote::{format_ident, quote};
+use syn::{Ident, Visibility};
+
+/// The smallest unsigned integer with a bit for each variant.
+fn backing(count: usize) -> Ident {
+    let bits = [8, 16, 32, 64, 128]
+        .into_iter()
+        .find(|bits| count <= *bits)
+        .unwrap_or(128);
+
+    format_ident!("u{}", bits)
+}
+
+pub fn set_ident(enum_name: &Ident) -> Ident {
+    format_ident!("{}Set", enum_name)
+}
+
+/// A `<Enum>Set` bitset of the variants of an enum without fields, written and parsed as a list joined by `separator`.
+pub fn set_impl(
+    enum_name: &Ident,
+    vis: &Visibility,
+    count: usize,
+    separator: &str,
+    trim: bool,
+) -> proc_macro2::TokenStream {
+    let set_name = set_ident(enum_name);
+    let error_name = error_ident(enum_name);
+    // Elements are trimmed like `parse_list`'s, following `list(trim = ...)`
+    let (input, trim) = match trim {
+        true => (quote!(s.trim()), quote!(.map(str::trim))),
+        false => (quote!(s), quote!()),
+    };
+    let backing = backing(count);
+    let all = proc_macro2::Literal::u128_unsuffixed(u128::MAX >> (128 - count));
+    let doc = format!(
+        "A set of [`{}`] variants, stored as one bit per variant.",
+        enum_name
+    );
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        #vis struct #set_name(#backing);
+
+        impl #set_name {
+            /// A set with no variants.
+            pub const fn new() -> Self {
+                #set_name(0)
+            }
+
+            /// A set with every variant.
+            pub const fn all() -> Self {
+                #set_name(#all)
+            }
+
+            const fn bit(value: &#enum_name) -> #backing {
+                1 << value.index()
+            }
+
+            /// Adds a variant, returning whether it wasn't already present.
+            pub fn insert(&mut self, value: #enum_name) -> bool {
+                let present = self.contains(&value);
+                self.0 |= Self::bit(&value);
+                !present
+            }
+
+            /// Removes a variant, returning whether it was present.
+            pub fn remove(&mut self, value: &#enum_name) -> bool {
+                let present = self.contains(value);
+                self.0 &= !Self::bit(value);
+                present
+            }
+
+            pub const fn contains(&self, value: &#enum_name) -> bool {
+                self.0 & Self::bit(value) != 0
+            }
+
+            pub const fn len(&self) -> usize {
+                self.0.count_ones() as usize
+            }
+
+            pub const fn is_empty(&self) -> bool {
+                self.0 == 0
+            }
+
+            /// The variants in the set, in declaration order.
+            pub fn iter(&self) -> impl DoubleEndedIterator<Item = #enum_name> {
+                let bits = self.0;
+                (0..#count)
+                    .filter(move |i| bits & (1 << i) != 0)
+                    .filter_map(#enum_name::from_index)
+            }
+
+            pub const fn union(self, other: Self) -> Self {
+                #set_name(self.0 | other.0)
+            }
+
+            pub const fn intersection(self, other: Self) -> Self {
+                #set_name(self.0 & other.0)
+            }
+
+            pub const fn difference(self, other: Self) -> Self {
+                #set_name(self.0 & !other.0)
+            }
+        }
+
+        impl core::iter::FromIterator<#enum_name> for #set_name {
+            fn from_iter<I: IntoIterator<Item = #enum_name>>(iter: I) -> Self {
+                let mut set = #set_name::new();
+                set.extend(iter);
+                set
+            }
+        }
+
+        impl core::iter::Extend<#enum_name> for #set_name {
+            fn extend<I: IntoIterator<Item = #enum_name>>(&mut self, iter: I) {
+                for value in iter {
+                    self.insert(value);
+                }
+            }
+        }
+
+        impl core::fmt::Display for #set_name {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                for (i, value) in self.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(#separator)?;
+                    }
+                    f.write_str(value.as_str())?;
+                }
+
+                Ok(())
+            }
+        }
+
+        impl core::str::FromStr for #set_name {
+            type Err = #error_name;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                if #input.is_empty() {
+                    return Ok(#set_name::new());
+                }
+
+                s.split(#separator)
+                    #trim
+                    .map(<#enum_name as core::str::FromStr>::from_str)
+                    .collect()
+            }
+        }
+    }
+}
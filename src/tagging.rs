@@ -0,0 +1,1191 @@
+use crate::{
+    generics::{split, with_lifetime, Bounds},
+    mode::{self, CaseMode, ParseMode},
+    payload::{self, bindings, is_phantom},
+    prefix,
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    ext::IdentExt, meta::ParseNestedMeta, DataEnum, Field, Fields, Generics, Ident, LitStr, Variant,
+};
+
+/// How a variant and its fields are laid out, set through `serde = "external"` or `serde(tag = "...")`.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Tagging {
+    /// `{"variant": fields}`, or just `"variant"` for a unit variant.
+    External,
+    /// `{"tag": "variant", ...fields}`.
+    Internal { tag: String },
+    /// `{"tag": "variant", "content": fields}`.
+    Adjacent { tag: String, content: String },
+}
+
+impl Tagging {
+    /// Reads `serde(tag = "...")` or `serde(tag = "...", content = "...")`.
+    pub fn parse(meta: &ParseNestedMeta) -> syn::Result<Self> {
+        let mut tag = None;
+        let mut content = None;
+        meta.parse_nested_meta(|meta| {
+            if meta.path.is_ident("tag") {
+                tag = Some(meta.value()?.parse::<LitStr>()?);
+            } else if meta.path.is_ident("content") {
+                content = Some(meta.value()?.parse::<LitStr>()?);
+            } else {
+                return Err(meta.error("expected `tag = \"...\"` or `content = \"...\"`"));
+            }
+
+            Ok(())
+        })?;
+
+        match (tag, content) {
+            (Some(tag), None) => Ok(Tagging::Internal { tag: tag.value() }),
+            (Some(tag), Some(content)) if tag.value() == content.value() => Err(syn::Error::new(
+                content.span(),
+                "`tag` and `content` need different names",
+            )),
+            (Some(tag), Some(content)) => Ok(Tagging::Adjacent {
+                tag: tag.value(),
+                content: content.value(),
+            }),
+            (None, Some(content)) => Err(syn::Error::new(
+                content.span(),
+                "`content` needs a `tag` as well",
+            )),
+            (None, None) => Err(meta.error("expected `tag = \"...\"`")),
+        }
+    }
+}
+
+/// How each variant is written and read, the tag being read the same way `FromStr` reads a variant's name.
+pub struct Spellings<'a> {
+    /// Each variant's `as_str` spelling, which the tag is written as.
+    pub names: &'a [String],
+    /// The spellings `FromStr` accepts for each variant, empty for rejected variants.
+    pub accepted: &'a [Vec<String>],
+    /// Each variant's `#[parse(...)]` options.
+    pub modes: &'a [ParseMode],
+    /// The type `allow_numeric` discriminants are compared as.
+    pub repr: &'a Ident,
+}
+
+/// The shape of a variant's payload, leaving out `PhantomData` fields.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    Unit,
+    Newtype,
+    Tuple,
+    Struct,
+}
+
+impl Shape {
+    pub fn of(variant: &Variant) -> Self {
+        match (&variant.fields, payload::payload_fields(variant).count()) {
+            (_, 0) => Shape::Unit,
+            (Fields::Unnamed(_), 1) => Shape::Newtype,
+            (Fields::Unnamed(_), _) => Shape::Tuple,
+            _ => Shape::Struct,
+        }
+    }
+}
+
+/// The binding of each field written and read, alongside the field.
+fn fields(variant: &Variant) -> Vec<(Ident, &Field)> {
+    bindings(variant, "f")
+        .into_iter()
+        .zip(&variant.fields)
+        .filter(|(_, field)| !is_phantom(&field.ty))
+        .collect()
+}
+
+/// The key a struct variant's field is written under.
+pub fn field_name(field: &Field) -> String {
+    field
+        .ident
+        .as_ref()
+        .map(|ident| ident.unraw().to_string())
+        .unwrap_or_default()
+}
+
+/// The variant built from its `__f<index>` bindings, with `PhantomData` for the fields that aren't read.
+fn construct(enum_name: &Ident, variant: &Variant) -> TokenStream {
+    let read = fields(variant)
+        .into_iter()
+        .map(|(binding, _)| binding)
+        .collect::<Vec<_>>();
+    payload::construct(enum_name, variant, |binding| match read.contains(binding) {
+        true => quote!(#binding),
+        false => quote!(core::marker::PhantomData),
+    })
+}
+
+/// Statements writing a struct's fields through the `serde::ser` trait `state` implements.
+fn serialize_fields(variant: &Variant, state: TokenStream) -> Vec<TokenStream> {
+    fields(variant)
+        .into_iter()
+        .map(|(binding, field)| {
+            let key = field_name(field);
+            quote!(#state::serialize_field(&mut state, #key, #binding)?;)
+        })
+        .collect()
+}
+
+/// The body of `Serialize::serialize`, writing the tag as the variant's `as_str` spelling.
+fn serialize(
+    enum_name: &Ident,
+    data: &DataEnum,
+    tagging: &Tagging,
+    names: &[String],
+) -> TokenStream {
+    let arms = data.variants.iter().zip(names).enumerate().map(|(i, (variant, name))| {
+        let index = i as u32;
+        let pattern = payload::pattern(enum_name, variant, "f");
+        let fields = fields(variant);
+        let len = fields.len();
+        let bindings = fields.iter().map(|(binding, _)| binding);
+        let body = match (tagging, Shape::of(variant)) {
+            (Tagging::External, Shape::Unit) => quote! {
+                serializer.serialize_unit_variant(stringify!(#enum_name), #index, #name)
+            },
+            (Tagging::External, Shape::Newtype) => quote! {
+                serializer.serialize_newtype_variant(stringify!(#enum_name), #index, #name, #(#bindings)*)
+            },
+            (Tagging::External, Shape::Tuple) => quote! {{
+                let mut state = serializer.serialize_tuple_variant(stringify!(#enum_name), #index, #name, #len)?;
+                #(serde::ser::SerializeTupleVariant::serialize_field(&mut state, #bindings)?;)*
+                serde::ser::SerializeTupleVariant::end(state)
+            }},
+            (Tagging::External, Shape::Struct) => {
+                let fields = serialize_fields(variant, quote!(serde::ser::SerializeStructVariant));
+                quote! {{
+                    let mut state = serializer.serialize_struct_variant(stringify!(#enum_name), #index, #name, #len)?;
+                    #(#fields)*
+                    serde::ser::SerializeStructVariant::end(state)
+                }}
+            }
+            (Tagging::Internal { tag }, _) => {
+                let fields = serialize_fields(variant, quote!(serde::ser::SerializeStruct));
+                quote! {{
+                    let mut state = serializer.serialize_struct(stringify!(#enum_name), #len + 1)?;
+                    serde::ser::SerializeStruct::serialize_field(&mut state, #tag, #name)?;
+                    #(#fields)*
+                    serde::ser::SerializeStruct::end(state)
+                }}
+            }
+            (Tagging::Adjacent { tag, .. }, Shape::Unit) => quote! {{
+                let mut state = serializer.serialize_struct(stringify!(#enum_name), 1)?;
+                serde::ser::SerializeStruct::serialize_field(&mut state, #tag, #name)?;
+                serde::ser::SerializeStruct::end(state)
+            }},
+            (Tagging::Adjacent { tag, content: key }, _) => quote! {{
+                let mut state = serializer.serialize_struct(stringify!(#enum_name), 2)?;
+                serde::ser::SerializeStruct::serialize_field(&mut state, #tag, #name)?;
+                serde::ser::SerializeStruct::serialize_field(&mut state, #key, &Content(self))?;
+                serde::ser::SerializeStruct::end(state)
+            }},
+        };
+
+        quote!(#pattern => #body,)
+    });
+
+    quote! {
+        match self {
+            #(#arms)*
+        }
+    }
+}
+
+/// A wrapper serializing only the fields of the variant it borrows, for the content of an adjacently tagged enum.
+fn content(
+    enum_name: &Ident,
+    data: &DataEnum,
+    bounds: &Bounds,
+    serialize_generics: &Generics,
+    names: &[String],
+) -> TokenStream {
+    let lifetime = bounds.fresh_lifetime();
+    let generics = with_lifetime(serialize_generics, &lifetime);
+    let (params, where_clause) = split(&generics);
+    let (_, content_ty, _) = generics.split_for_impl();
+    let (_, ty_generics, _) = serialize_generics.split_for_impl();
+
+    let arms = data.variants.iter().zip(names).map(|(variant, name)| {
+        let pattern = payload::pattern(enum_name, variant, "f");
+        let fields = fields(variant);
+        let len = fields.len();
+        let bindings = fields.iter().map(|(binding, _)| binding);
+        let body = match Shape::of(variant) {
+            Shape::Unit => quote!(serializer.serialize_unit()),
+            Shape::Newtype => quote!(serde::Serialize::serialize(#(#bindings)*, serializer)),
+            Shape::Tuple => quote! {{
+                let mut state = serializer.serialize_tuple(#len)?;
+                #(serde::ser::SerializeTuple::serialize_element(&mut state, #bindings)?;)*
+                serde::ser::SerializeTuple::end(state)
+            }},
+            Shape::Struct => {
+                let fields = serialize_fields(variant, quote!(serde::ser::SerializeStruct));
+                quote! {{
+                    let mut state = serializer.serialize_struct(#name, #len)?;
+                    #(#fields)*
+                    serde::ser::SerializeStruct::end(state)
+                }}
+            }
+        };
+
+        quote!(#pattern => #body,)
+    });
+
+    quote! {
+        struct Content #params (&#lifetime #enum_name #ty_generics) #where_clause;
+
+        impl #params serde::Serialize for Content #content_ty #where_clause {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                match self.0 {
+                    #(#arms)*
+                }
+            }
+        }
+    }
+}
+
+/// A match on the variant index `tag`, with `fallback` for the indexes without an arm.
+fn match_tag(arms: Vec<TokenStream>, fallback: TokenStream) -> TokenStream {
+    match arms.is_empty() {
+        true => fallback,
+        false => quote! {
+            match tag {
+                #(#arms)*
+                _ => #fallback,
+            }
+        },
+    }
+}
+
+/// The body of `Visitor::visit_str` for the tag, matching `v` against each variant's spellings the
+/// way `FromStr` does: exact spellings and prefixes first, then case insensitive spellings, then
+/// discriminants.
+fn read_tag(enum_name: &Ident, data: &DataEnum, spellings: &Spellings) -> TokenStream {
+    let variants = data
+        .variants
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !spellings.accepted[*i].is_empty())
+        .collect::<Vec<_>>();
+    let tags = variants
+        .iter()
+        .map(|(i, _)| &spellings.names[*i])
+        .collect::<Vec<_>>();
+
+    let mut seen = Vec::new();
+    let mut arms = variants
+        .iter()
+        .filter_map(|(i, _)| {
+            let accepted = std::iter::once(&spellings.names[*i])
+                .chain(&spellings.accepted[*i])
+                .filter(|spelling| !seen.contains(*spelling))
+                .cloned()
+                .collect::<Vec<_>>();
+            seen.extend(accepted.iter().cloned());
+            (!accepted.is_empty()).then(|| quote!(#(#accepted)|* => return Ok(#i),))
+        })
+        .collect::<Vec<_>>();
+
+    // Like `FromStr`, only variants without fields take prefixes
+    let prefixed = variants
+        .iter()
+        .filter(|(i, variant)| spellings.modes[*i].prefix && !payload::has_fields(variant))
+        .flat_map(|(i, _)| {
+            spellings.accepted[*i]
+                .iter()
+                .map(move |spelling| (*i, spelling))
+        })
+        .collect::<Vec<_>>();
+    let all = variants
+        .iter()
+        .flat_map(|(i, _)| {
+            spellings.accepted[*i]
+                .iter()
+                .map(move |spelling| (*i, spelling))
+        })
+        .collect::<Vec<_>>();
+    let cased = all
+        .iter()
+        .map(|(i, spelling)| (spelling.as_str(), spellings.modes[*i].case))
+        .collect::<Vec<_>>();
+    let prefixes = prefix::prefixes(
+        prefixed.iter().map(|(_, spelling)| spelling.as_str()),
+        &cased,
+    );
+    for (prefix, matches) in prefixes {
+        let (i, _) = all[matches[0]];
+        if matches.iter().all(|&other| all[other].0 == i) {
+            arms.push(quote!(#prefix => return Ok(#i),));
+            continue;
+        }
+
+        let candidates = matches
+            .iter()
+            .map(|&other| all[other].1.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let message = format!(
+            "ambiguous variant `{}`, could be any of: {}",
+            prefix, candidates
+        );
+        arms.push(quote!(#prefix => return Err(E::custom(#message)),));
+    }
+
+    let relaxed = variants.iter().flat_map(|(i, _)| {
+        let case = spellings.modes[*i].case;
+        spellings.accepted[*i]
+            .iter()
+            .filter(move |_| case != CaseMode::Exact)
+            .map(move |spelling| {
+                let matches = case.compare(quote!(v), spelling);
+                quote!(if #matches {
+                    return Ok(#i);
+                })
+            })
+    });
+    let words_eq = variants
+        .iter()
+        .any(|(i, _)| spellings.modes[*i].case == CaseMode::AnyCase)
+        .then(mode::words_eq);
+
+    // `allow_numeric` is only allowed on enums without fields, so the variants can be cast
+    let repr = spellings.repr;
+    let numeric = variants
+        .iter()
+        .filter(|(i, _)| spellings.modes[*i].numeric)
+        .map(|(i, variant)| {
+            let ident = &variant.ident;
+            quote!(if value == #enum_name::#ident as #repr {
+                return Ok(#i);
+            })
+        })
+        .collect::<Vec<_>>();
+    let numeric = (!numeric.is_empty()).then(|| {
+        quote! {
+            if let Ok(value) = v.parse::<#repr>() {
+                #(#numeric)*
+            }
+        }
+    });
+
+    quote! {
+        match v {
+            #(#arms)*
+            _ => {}
+        }
+
+        #words_eq
+        #(#relaxed)*
+        #numeric
+        Err(E::unknown_variant(v, &[#(#tags),*]))
+    }
+}
+
+/// A self-describing copy of the values read before the tag, replayed into the variant's seed once
+/// the tag is known. Borrowed strings and bytes stay borrowed. `Chain` replays buffered entries
+/// ahead of the rest of the map.
+fn buffer(alloc: &TokenStream) -> TokenStream {
+    quote! {
+        enum Buffered<'de> {
+            Bool(bool),
+            I64(i64),
+            U64(u64),
+            I128(i128),
+            U128(u128),
+            F64(f64),
+            Char(char),
+            Str(&'de str),
+            String(#alloc::string::String),
+            Bytes(&'de [u8]),
+            ByteBuf(#alloc::vec::Vec<u8>),
+            None,
+            Some(#alloc::boxed::Box<Buffered<'de>>),
+            Unit,
+            Seq(#alloc::vec::Vec<Buffered<'de>>),
+            Map(#alloc::vec::Vec<(Buffered<'de>, Buffered<'de>)>),
+        }
+
+        impl Buffered<'_> {
+            fn as_str(&self) -> Option<&str> {
+                match self {
+                    Buffered::Str(v) => Some(v),
+                    Buffered::String(v) => Some(v),
+                    _ => None,
+                }
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for Buffered<'de> {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_any(BufferedVisitor)
+            }
+        }
+
+        struct BufferedVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for BufferedVisitor {
+            type Value = Buffered<'de>;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.write_str("any value")
+            }
+
+            fn visit_bool<E: serde::de::Error>(self, v: bool) -> Result<Buffered<'de>, E> {
+                Ok(Buffered::Bool(v))
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Buffered<'de>, E> {
+                Ok(Buffered::I64(v))
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Buffered<'de>, E> {
+                Ok(Buffered::U64(v))
+            }
+
+            fn visit_i128<E: serde::de::Error>(self, v: i128) -> Result<Buffered<'de>, E> {
+                Ok(Buffered::I128(v))
+            }
+
+            fn visit_u128<E: serde::de::Error>(self, v: u128) -> Result<Buffered<'de>, E> {
+                Ok(Buffered::U128(v))
+            }
+
+            fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<Buffered<'de>, E> {
+                Ok(Buffered::F64(v))
+            }
+
+            fn visit_char<E: serde::de::Error>(self, v: char) -> Result<Buffered<'de>, E> {
+                Ok(Buffered::Char(v))
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Buffered<'de>, E> {
+                Ok(Buffered::String(v.into()))
+            }
+
+            fn visit_borrowed_str<E: serde::de::Error>(self, v: &'de str) -> Result<Buffered<'de>, E> {
+                Ok(Buffered::Str(v))
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Buffered<'de>, E> {
+                Ok(Buffered::ByteBuf(v.into()))
+            }
+
+            fn visit_borrowed_bytes<E: serde::de::Error>(self, v: &'de [u8]) -> Result<Buffered<'de>, E> {
+                Ok(Buffered::Bytes(v))
+            }
+
+            fn visit_none<E: serde::de::Error>(self) -> Result<Buffered<'de>, E> {
+                Ok(Buffered::None)
+            }
+
+            fn visit_some<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<Buffered<'de>, D::Error> {
+                serde::Deserialize::deserialize(deserializer).map(|v| Buffered::Some(#alloc::boxed::Box::new(v)))
+            }
+
+            fn visit_unit<E: serde::de::Error>(self) -> Result<Buffered<'de>, E> {
+                Ok(Buffered::Unit)
+            }
+
+            fn visit_newtype_struct<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<Buffered<'de>, D::Error> {
+                serde::Deserialize::deserialize(deserializer)
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Buffered<'de>, A::Error> {
+                let mut values = #alloc::vec::Vec::new();
+                while let Some(value) = seq.next_element()? {
+                    values.push(value);
+                }
+                Ok(Buffered::Seq(values))
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Buffered<'de>, A::Error> {
+                let mut entries = #alloc::vec::Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(Buffered::Map(entries))
+            }
+        }
+
+        impl<'de, E: serde::de::Error> serde::de::IntoDeserializer<'de, E> for Buffered<'de> {
+            type Deserializer = Replay<'de, E>;
+
+            fn into_deserializer(self) -> Replay<'de, E> {
+                Replay(self, core::marker::PhantomData)
+            }
+        }
+
+        struct Replay<'de, E>(Buffered<'de>, core::marker::PhantomData<E>);
+
+        impl<'de, E: serde::de::Error> serde::Deserializer<'de> for Replay<'de, E> {
+            type Error = E;
+
+            fn deserialize_any<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+                match self.0 {
+                    Buffered::Bool(v) => visitor.visit_bool(v),
+                    Buffered::I64(v) => visitor.visit_i64(v),
+                    Buffered::U64(v) => visitor.visit_u64(v),
+                    Buffered::I128(v) => visitor.visit_i128(v),
+                    Buffered::U128(v) => visitor.visit_u128(v),
+                    Buffered::F64(v) => visitor.visit_f64(v),
+                    Buffered::Char(v) => visitor.visit_char(v),
+                    Buffered::Str(v) => visitor.visit_borrowed_str(v),
+                    Buffered::String(v) => visitor.visit_str(&v),
+                    Buffered::Bytes(v) => visitor.visit_borrowed_bytes(v),
+                    Buffered::ByteBuf(v) => visitor.visit_bytes(&v),
+                    Buffered::None => visitor.visit_none(),
+                    Buffered::Some(v) => visitor.visit_some(Replay(*v, core::marker::PhantomData)),
+                    Buffered::Unit => visitor.visit_unit(),
+                    Buffered::Seq(v) => {
+                        let mut seq = serde::de::value::SeqDeserializer::new(v.into_iter());
+                        let value = visitor.visit_seq(&mut seq)?;
+                        seq.end()?;
+                        Ok(value)
+                    }
+                    Buffered::Map(v) => {
+                        let mut map = serde::de::value::MapDeserializer::new(v.into_iter());
+                        let value = visitor.visit_map(&mut map)?;
+                        map.end()?;
+                        Ok(value)
+                    }
+                }
+            }
+
+            fn deserialize_option<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+                match self.0 {
+                    Buffered::None | Buffered::Unit => visitor.visit_none(),
+                    Buffered::Some(v) => visitor.visit_some(Replay(*v, core::marker::PhantomData)),
+                    v => visitor.visit_some(Replay(v, core::marker::PhantomData)),
+                }
+            }
+
+            fn deserialize_newtype_struct<V: serde::de::Visitor<'de>>(
+                self,
+                _name: &'static str,
+                visitor: V,
+            ) -> Result<V::Value, E> {
+                visitor.visit_newtype_struct(self)
+            }
+
+            fn deserialize_enum<V: serde::de::Visitor<'de>>(
+                self,
+                _name: &'static str,
+                _variants: &'static [&'static str],
+                visitor: V,
+            ) -> Result<V::Value, E> {
+                match self.0 {
+                    Buffered::Str(v) => visitor.visit_enum(serde::de::IntoDeserializer::<E>::into_deserializer(v)),
+                    Buffered::String(v) => visitor.visit_enum(serde::de::IntoDeserializer::<E>::into_deserializer(v.as_str())),
+                    v => Replay(v, core::marker::PhantomData).deserialize_any(visitor),
+                }
+            }
+
+            serde::forward_to_deserialize_any! {
+                bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+                bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+            }
+        }
+
+        struct Chain<'de, A> {
+            buffered: #alloc::vec::IntoIter<(Buffered<'de>, Buffered<'de>)>,
+            value: Option<Buffered<'de>>,
+            map: A,
+        }
+
+        impl<'de, A: serde::de::MapAccess<'de>> serde::de::MapAccess<'de> for Chain<'de, A> {
+            type Error = A::Error;
+
+            fn next_key_seed<K: serde::de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, A::Error> {
+                match self.buffered.next() {
+                    Some((key, value)) => {
+                        self.value = Some(value);
+                        seed.deserialize(Replay(key, core::marker::PhantomData)).map(Some)
+                    }
+                    None => self.map.next_key_seed(seed),
+                }
+            }
+
+            fn next_value_seed<V: serde::de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, A::Error> {
+                match self.value.take() {
+                    Some(value) => seed.deserialize(Replay(value, core::marker::PhantomData)),
+                    None => self.map.next_value_seed(seed),
+                }
+            }
+        }
+    }
+}
+
+/// The body of `Deserialize::deserialize`. The tag is read into the variant's index, accepting the
+/// `as_str` spelling and the spellings `FromStr` accepts, then the fields are read through a seed
+/// for that variant. With an allocator, whatever comes before an internal or adjacent tag is
+/// buffered, without one the tag has to come first, as it does in everything `serialize` writes.
+fn deserialize(
+    enum_name: &Ident,
+    data: &DataEnum,
+    tagging: &Tagging,
+    spellings: &Spellings,
+    alloc: Option<&TokenStream>,
+    deserialize_generics: &Generics,
+    ty_generics: &TokenStream,
+) -> TokenStream {
+    let (params, where_clause) = split(deserialize_generics);
+    let (_, de_ty, _) = deserialize_generics.split_for_impl();
+    let turbofish = de_ty.as_turbofish();
+    let expecting = format!("a {} variant", enum_name);
+    let expecting_fields = format!("the fields of a {} variant", enum_name);
+    let names = spellings.names;
+
+    let variants = data
+        .variants
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !spellings.accepted[*i].is_empty())
+        .collect::<Vec<_>>();
+    let tags = variants.iter().map(|(i, _)| &names[*i]).collect::<Vec<_>>();
+    let visit_str = read_tag(enum_name, data, spellings);
+    let index_arms = variants.iter().map(|(i, _)| {
+        let index = *i as u64;
+        quote!(#index => Ok(#i),)
+    });
+
+    let shapes = variants
+        .iter()
+        .map(|(i, variant)| (*i, *variant, Shape::of(variant)))
+        .collect::<Vec<_>>();
+    let has = |shape| shapes.iter().any(|(_, _, other)| *other == shape);
+    let keys = |variant: &Variant| {
+        fields(variant)
+            .into_iter()
+            .map(|(_, field)| field_name(field))
+            .collect::<Vec<_>>()
+    };
+
+    let seed_arms = shapes.iter().map(|(i, variant, shape)| {
+        let value = construct(enum_name, variant);
+        let fields = fields(variant);
+        let body = match shape {
+            Shape::Unit => quote! {{
+                <() as serde::Deserialize>::deserialize(deserializer)?;
+                Ok(#value)
+            }},
+            Shape::Newtype => {
+                let (binding, field) = &fields[0];
+                let ty = &field.ty;
+                quote! {{
+                    let #binding = <#ty as serde::Deserialize>::deserialize(deserializer)?;
+                    Ok(#value)
+                }}
+            }
+            Shape::Tuple => {
+                let len = fields.len();
+                quote!(deserializer.deserialize_tuple(#len, self))
+            }
+            Shape::Struct => {
+                let name = &names[*i];
+                let keys = keys(variant);
+                quote!(deserializer.deserialize_struct(#name, &[#(#keys),*], self))
+            }
+        };
+
+        quote!(#i => #body,)
+    });
+
+    let seq_arms = shapes
+        .iter()
+        .filter(|(_, _, shape)| matches!(shape, Shape::Tuple | Shape::Struct))
+        .map(|(i, variant, _)| {
+            let value = construct(enum_name, variant);
+            let reads = fields(variant).into_iter().enumerate().map(
+                |(position, (binding, field))| {
+                    let ty = &field.ty;
+                    quote! {
+                        let #binding = match seq.next_element::<#ty>()? {
+                            Some(value) => value,
+                            None => return Err(serde::de::Error::invalid_length(#position, &self)),
+                        };
+                    }
+                },
+            );
+
+            quote! {
+                #i => {
+                    #(#reads)*
+                    Ok(#value)
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let map_arms = shapes
+        .iter()
+        .filter(|(_, _, shape)| match tagging {
+            Tagging::Internal { .. } => true,
+            _ => *shape == Shape::Struct,
+        })
+        .map(|(i, variant, _)| {
+            let value = construct(enum_name, variant);
+            let fields = fields(variant);
+            let keys = keys(variant);
+            let declarations = fields.iter().map(|(binding, field)| {
+                let ty = &field.ty;
+                quote!(let mut #binding: Option<#ty> = None;)
+            });
+            let entries =
+                fields
+                    .iter()
+                    .zip(&keys)
+                    .enumerate()
+                    .map(|(position, ((binding, _), key))| {
+                        quote! {
+                            Some(#position) => {
+                                if #binding.is_some() {
+                                    return Err(serde::de::Error::duplicate_field(#key));
+                                }
+                                #binding = Some(map.next_value()?);
+                            }
+                        }
+                    });
+            let unwraps = fields.iter().zip(&keys).map(|((binding, _), key)| {
+                quote! {
+                    let #binding = match #binding {
+                        Some(value) => value,
+                        None => return Err(serde::de::Error::missing_field(#key)),
+                    };
+                }
+            });
+
+            quote! {
+                #i => {
+                    #(#declarations)*
+                    while let Some(key) = map.next_key_seed(Key(&[#(#keys),*]))? {
+                        match key {
+                            #(#entries)*
+                            _ => {
+                                map.next_value::<serde::de::IgnoredAny>()?;
+                            }
+                        }
+                    }
+                    #(#unwraps)*
+                    Ok(#value)
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let unit_arms = shapes
+        .iter()
+        .filter(|(_, _, shape)| *shape == Shape::Unit)
+        .map(|(i, variant, _)| {
+            let value = construct(enum_name, variant);
+            quote!(#i => Ok(#value),)
+        })
+        .collect::<Vec<_>>();
+
+    let uses_payload = !matches!(tagging, Tagging::External)
+        || shapes.iter().any(|(_, _, shape)| *shape != Shape::Unit);
+    let uses_key = !matches!(tagging, Tagging::External) || has(Shape::Struct);
+
+    let key = uses_key.then(|| {
+        quote! {
+            struct Key(&'static [&'static str]);
+
+            impl<'de> serde::de::DeserializeSeed<'de> for Key {
+                type Value = Option<usize>;
+
+                fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+                    deserializer.deserialize_identifier(self)
+                }
+            }
+
+            impl<'de> serde::de::Visitor<'de> for Key {
+                type Value = Option<usize>;
+
+                fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                    f.write_str("a field name")
+                }
+
+                fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                    Ok(self.0.iter().position(|key| *key == v))
+                }
+
+                fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                    Ok(self.0.iter().position(|key| key.as_bytes() == v))
+                }
+
+                fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                    Ok(usize::try_from(v).ok().filter(|v| *v < self.0.len()))
+                }
+            }
+        }
+    });
+
+    let payload = uses_payload.then(|| {
+        let seq = match seq_arms.is_empty() {
+            true => quote!(),
+            false => quote! {
+                fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                    match self.0 {
+                        #(#seq_arms)*
+                        _ => Err(serde::de::Error::invalid_type(serde::de::Unexpected::Seq, &self)),
+                    }
+                }
+            },
+        };
+        let map = match map_arms.is_empty() {
+            true => quote!(),
+            false => quote! {
+                fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                    match self.0 {
+                        #(#map_arms)*
+                        _ => Err(serde::de::Error::invalid_type(serde::de::Unexpected::Map, &self)),
+                    }
+                }
+            },
+        };
+
+        quote! {
+            struct Payload #params (usize, core::marker::PhantomData<(fn() -> #enum_name #ty_generics, &'de ())>) #where_clause;
+
+            impl #params serde::de::DeserializeSeed<'de> for Payload #de_ty #where_clause {
+                type Value = #enum_name #ty_generics;
+
+                fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+                    match self.0 {
+                        #(#seed_arms)*
+                        _ => unreachable!(),
+                    }
+                }
+            }
+
+            impl #params serde::de::Visitor<'de> for Payload #de_ty #where_clause {
+                type Value = #enum_name #ty_generics;
+
+                fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                    f.write_str(#expecting_fields)
+                }
+
+                #seq
+                #map
+            }
+        }
+    });
+
+    let payload_seed = quote!(Payload #turbofish(tag, core::marker::PhantomData));
+    let buffer = alloc
+        .filter(|_| !matches!(tagging, Tagging::External))
+        .map(buffer);
+    // Only an external tag is the variant's identifier, the others are written as strings.
+    let read_tag = match tagging {
+        Tagging::External => quote!(deserializer.deserialize_identifier(self)),
+        _ => quote!(deserializer.deserialize_str(self)),
+    };
+    let (visit, call) = match tagging {
+        Tagging::External => {
+            let arms = shapes.iter().map(|(i, variant, shape)| {
+                let body = match shape {
+                    Shape::Unit => {
+                        let value = construct(enum_name, variant);
+                        quote! {{
+                            serde::de::VariantAccess::unit_variant(variant)?;
+                            Ok(#value)
+                        }}
+                    }
+                    Shape::Newtype => quote! {
+                        serde::de::VariantAccess::newtype_variant_seed(variant, #payload_seed)
+                    },
+                    Shape::Tuple => {
+                        let len = fields(variant).len();
+                        quote! {
+                            serde::de::VariantAccess::tuple_variant(variant, #len, #payload_seed)
+                        }
+                    }
+                    Shape::Struct => {
+                        let keys = keys(variant);
+                        quote! {
+                            serde::de::VariantAccess::struct_variant(variant, &[#(#keys),*], #payload_seed)
+                        }
+                    }
+                };
+
+                quote!(#i => #body,)
+            });
+
+            (
+                quote! {
+                    fn visit_enum<A: serde::de::EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+                        let (tag, variant) = data.variant_seed(Tag)?;
+                        match tag {
+                            #(#arms)*
+                            _ => unreachable!(),
+                        }
+                    }
+                },
+                quote! {
+                    deserializer.deserialize_enum(stringify!(#enum_name), &[#(#tags),*], visitor)
+                },
+            )
+        }
+        Tagging::Internal { tag } => {
+            let read = match alloc {
+                Some(alloc) => quote! {
+                    let mut buffered = #alloc::vec::Vec::new();
+                    let tag = loop {
+                        match map.next_key::<Buffered>()? {
+                            Some(key) if key.as_str() == Some(#tag) => break map.next_value_seed(Tag)?,
+                            Some(key) => buffered.push((key, map.next_value()?)),
+                            None => return Err(serde::de::Error::missing_field(#tag)),
+                        }
+                    };
+                    let map = Chain {
+                        buffered: buffered.into_iter(),
+                        value: None,
+                        map,
+                    };
+                },
+                None => quote! {
+                    match map.next_key_seed(Key(&[#tag]))? {
+                        Some(Some(_)) => {}
+                        Some(None) => {
+                            return Err(serde::de::Error::custom(format_args!("expected `{}` before any other field", #tag)))
+                        }
+                        None => return Err(serde::de::Error::missing_field(#tag)),
+                    }
+                    let tag = map.next_value_seed(Tag)?;
+                },
+            };
+
+            (
+                quote! {
+                    fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                        #read
+                        serde::de::Visitor::visit_map(#payload_seed, map)
+                    }
+                },
+                quote!(deserializer.deserialize_any(visitor)),
+            )
+        }
+        Tagging::Adjacent {
+            tag: tag_key,
+            content,
+        } => {
+            let missing_content = match_tag(
+                unit_arms.clone(),
+                quote!(Err(serde::de::Error::missing_field(#content))),
+            );
+            let short_seq = match_tag(
+                unit_arms,
+                quote!(Err(serde::de::Error::invalid_length(1, &self))),
+            );
+
+            // The content can only be read once the tag says which variant it holds
+            let (early, duplicate, replay) = match alloc {
+                Some(_) => (
+                    quote!(buffered = Some(map.next_value::<Buffered>()?)),
+                    quote!(value.is_some() || buffered.is_some()),
+                    quote! {
+                        if let Some(buffered) = buffered {
+                            let replay = Replay::<A::Error>(buffered, core::marker::PhantomData);
+                            value = Some(serde::de::DeserializeSeed::deserialize(#payload_seed, replay)?);
+                        }
+                    },
+                ),
+                None => (
+                    quote! {
+                        return Err(serde::de::Error::custom(format_args!("expected `{}` before `{}`", #tag_key, #content)))
+                    },
+                    quote!(value.is_some()),
+                    quote!(),
+                ),
+            };
+            let buffered = alloc.map(|_| quote!(let mut buffered = None;));
+
+            (
+                quote! {
+                    fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                        let mut tag = None;
+                        let mut value = None;
+                        #buffered
+                        while let Some(key) = map.next_key_seed(Key(&[#tag_key, #content]))? {
+                            match key {
+                                Some(0) => {
+                                    if tag.is_some() {
+                                        return Err(serde::de::Error::duplicate_field(#tag_key));
+                                    }
+                                    tag = Some(map.next_value_seed(Tag)?);
+                                }
+                                Some(_) => {
+                                    if #duplicate {
+                                        return Err(serde::de::Error::duplicate_field(#content));
+                                    }
+                                    match tag {
+                                        Some(tag) => value = Some(map.next_value_seed(#payload_seed)?),
+                                        None => #early,
+                                    }
+                                }
+                                None => {
+                                    map.next_value::<serde::de::IgnoredAny>()?;
+                                }
+                            }
+                        }
+
+                        let tag = match tag {
+                            Some(tag) => tag,
+                            None => return Err(serde::de::Error::missing_field(#tag_key)),
+                        };
+                        #replay
+                        match value {
+                            Some(value) => Ok(value),
+                            None => #missing_content,
+                        }
+                    }
+
+                    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                        let tag = match seq.next_element_seed(Tag)? {
+                            Some(tag) => tag,
+                            None => return Err(serde::de::Error::invalid_length(0, &self)),
+                        };
+                        match seq.next_element_seed(#payload_seed)? {
+                            Some(value) => Ok(value),
+                            None => #short_seq,
+                        }
+                    }
+                },
+                quote! {
+                    deserializer.deserialize_struct(stringify!(#enum_name), &[#tag_key, #content], visitor)
+                },
+            )
+        }
+    };
+
+    quote! {
+        struct Tag;
+
+        impl<'de> serde::de::DeserializeSeed<'de> for Tag {
+            type Value = usize;
+
+            fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<usize, D::Error> {
+                #read_tag
+            }
+        }
+
+        impl<'de> serde::de::Visitor<'de> for Tag {
+            type Value = usize;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.write_str(#expecting)
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<usize, E> {
+                #visit_str
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<usize, E> {
+                match core::str::from_utf8(v) {
+                    Ok(v) => self.visit_str(v),
+                    Err(_) => Err(E::invalid_value(serde::de::Unexpected::Bytes(v), &self)),
+                }
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<usize, E> {
+                match v {
+                    #(#index_arms)*
+                    _ => Err(E::invalid_value(serde::de::Unexpected::Unsigned(v), &self)),
+                }
+            }
+        }
+
+        #key
+        #payload
+        #buffer
+
+        struct Visitor #params (core::marker::PhantomData<(fn() -> #enum_name #ty_generics, &'de ())>) #where_clause;
+
+        impl #params serde::de::Visitor<'de> for Visitor #de_ty #where_clause {
+            type Value = #enum_name #ty_generics;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.write_str(#expecting)
+            }
+
+            #visit
+        }
+
+        let visitor = Visitor #turbofish(core::marker::PhantomData);
+        #call
+    }
+}
+
+/// `Serialize` and `Deserialize` for a tagged enum, buffering values read before the tag when `alloc`
+/// is available. Rejected variants are written but never read.
+pub fn serde_impl(
+    enum_name: &Ident,
+    data: &DataEnum,
+    bounds: &Bounds,
+    tagging: &Tagging,
+    spellings: &Spellings,
+    alloc: Option<&TokenStream>,
+) -> TokenStream {
+    let serialize_generics = bounds.with(quote!(serde::Serialize), false);
+    let (serialize_params, serialize_where) = split(&serialize_generics);
+    let (_, ty_generics, _) = serialize_generics.split_for_impl();
+    let ty_generics = quote!(#ty_generics);
+
+    let deserialize_generics = with_lifetime(
+        &bounds.with(quote!(serde::Deserialize<'de>), true),
+        &syn::parse_quote!('de),
+    );
+    let (deserialize_params, deserialize_where) = split(&deserialize_generics);
+
+    let content = (matches!(tagging, Tagging::Adjacent { .. })
+        && data
+            .variants
+            .iter()
+            .any(|variant| Shape::of(variant) != Shape::Unit))
+    .then(|| {
+        content(
+            enum_name,
+            data,
+            bounds,
+            &serialize_generics,
+            spellings.names,
+        )
+    });
+    let serialize = serialize(enum_name, data, tagging, spellings.names);
+    let deserialize = deserialize(
+        enum_name,
+        data,
+        tagging,
+        spellings,
+        alloc,
+        &deserialize_generics,
+        &ty_generics,
+    );
+
+    quote! {
+        impl #serialize_params serde::Serialize for #enum_name #ty_generics #serialize_where {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                #content
+                #serialize
+            }
+        }
+
+        impl #deserialize_params serde::Deserialize<'de> for #enum_name #ty_generics #deserialize_where {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                #deserialize
+            }
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use crate::{
+    casing::parse_casing,
+    defaults::get_default_variant,
+    docs::description,
+    mode::{CaseMode, ParseMode},
+    options::Options,
+    payload,
+    rename::{get_aliases, get_rename},
+    serialization::Representation,
+    tagging::{field_name, Shape, Tagging},
+};
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::{punctuated::Punctuated, DataEnum, DeriveInput, Error, Ident, Token};
+
+/// Collects every error found, so they can be reported together.
+#[derive(Default)]
+pub struct Errors(Option<Error>);
+
+impl Errors {
+    pub fn push(&mut self, error: Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    pub fn check<T>(&mut self, result: syn::Result<T>) -> Option<T> {
+        result.map_err(|error| self.push(error)).ok()
+    }
+
+    pub fn finish(self) -> syn::Result<()> {
+        match self.0 {
+            Some(errors) => Err(errors),
+            None => Ok(()),
+        }
+    }
+}
+
+fn check_attrs(attrs: &[syn::Attribute], errors: &mut Errors) {
+    for attr in attrs {
+        let path = attr.path();
+        if path.is_ident("input_case") || path.is_ident("output_case") || path.is_ident("casing") {
+            let casings = attr
+                .parse_args_with(Punctuated::<syn::LitStr, Token![,]>::parse_separated_nonempty);
+            let casings = errors.check(casings).unwrap_or_default();
+            if casings.len() > 1 && !path.is_ident("input_case") {
+                errors.push(Error::new_spanned(
+                    &attr.meta,
+                    format!(
+                        "`{}` takes a single casing, only `input_case` accepts several",
+                        path.to_token_stream()
+                    ),
+                ));
+            }
+
+            for lit in casings {
+                errors.check(parse_casing(&lit));
+            }
+        } else if path.is_ident("reject") {
+            if attr.meta.require_path_only().is_err() {
+                errors.push(Error::new_spanned(
+                    &attr.meta,
+                    "`reject` does not take arguments",
+                ));
+            }
+        } else if path.is_ident("payload") {
+            errors.check(payload::check(attr));
+        } else if path.is_ident("parse") {
+            errors.check(ParseMode::check(attr));
+        }
+    }
+
+    errors.check(description(attrs));
+    errors.check(get_rename(attrs));
+    errors.check(get_aliases(attrs));
+}
+
+/// Whether `FromStr` accepts nothing but the listed spellings, compared exactly.
+fn exact_only(input: &DeriveInput, data: &DataEnum) -> bool {
+    let exact = data.variants.iter().all(|variant| {
+        ParseMode::resolve(&input.attrs, &variant.attrs)
+            .is_ok_and(|mode| mode.case == CaseMode::Exact && !mode.numeric && !mode.prefix)
+    });
+
+    exact && matches!(get_default_variant(&input.attrs), Ok(None))
+}
+
+/// Checks every attribute on the enum and its variants, before any code is generated.
+pub fn validate(input: &DeriveInput, data: &DataEnum) -> syn::Result<()> {
+    let mut errors = Errors::default();
+
+    check_attrs(&input.attrs, &mut errors);
+    let fieldless = data
+        .variants
+        .iter()
+        .all(|variant| variant.fields.is_empty());
+    let options = errors.check(Options::parse(&input.attrs));
+    if let Some(options) = &options {
+        if options.set.is_some() && !fieldless {
+            errors.push(Error::new(
+                input.ident.span(),
+                "`set` can only be used on enums without fields",
+            ));
+        } else if options.set.is_some() && !(1..=128).contains(&data.variants.len()) {
+            errors.push(Error::new(
+                input.ident.span(),
+                "`set` needs between 1 and 128 variants",
+            ));
+        }
+
+        if options.map && !fieldless {
+            errors.push(Error::new(
+                input.ident.span(),
+                "`map` can only be used on enums without fields",
+            ));
+        }
+
+        if options.clap && !cfg!(feature = "clap") {
+            errors.push(Error::new(
+                input.ident.span(),
+                "`clap` needs the `clap` feature of to_and_fro",
+            ));
+        } else if options.clap && !fieldless {
+            errors.push(Error::new(
+                input.ident.span(),
+                "`clap` can only be used on enums without fields",
+            ));
+        } else if options.clap && !exact_only(input, data) {
+            errors.push(Error::new(
+                input.ident.span(),
+                "`clap` only matches values exactly, so can't be used with `ignore_case`, `any_case`, `prefix`, `allow_numeric` or `default`",
+            ));
+        } else if options.clap && options.no_std {
+            errors.push(Error::new(
+                input.ident.span(),
+                "`clap` needs `std`, so can't be used with `no_std`",
+            ));
+        }
+
+        if options.alloc && !options.no_std {
+            errors.push(Error::new(
+                input.ident.span(),
+                "`alloc` only applies alongside `no_std`",
+            ));
+        }
+    }
+    let representation = errors.check(Representation::parse(&input.attrs)).flatten();
+    match (&representation, options.as_ref().and_then(|options| options.tagging.as_ref())) {
+        (Some(representation), Some(_)) if *representation != Representation::String => {
+            errors.push(Error::new(
+                input.ident.span(),
+                "`serde = \"...\"` and `serde(...)` can't be used together",
+            ))
+        }
+        (Some(Representation::Index | Representation::UnitVariant | Representation::Auto), None)
+            if !fieldless =>
+        {
+            errors.push(Error::new(
+                input.ident.span(),
+                "only `serde`, `serde = \"string\"`, `serde = \"external\"` or `serde(tag = \"...\")` can be used on enums with fields",
+            ))
+        }
+        (_, Some(Tagging::Internal { tag })) => {
+            for variant in &data.variants {
+                match Shape::of(variant) {
+                    Shape::Newtype | Shape::Tuple => errors.push(Error::new(
+                        variant.ident.span(),
+                        format!(
+                            "internally tagged enums can only have unit and struct variants, not {}",
+                            variant.ident
+                        ),
+                    )),
+                    _ if variant
+                        .fields
+                        .iter()
+                        .any(|field| field_name(field) == *tag) =>
+                    {
+                        errors.push(Error::new(
+                            variant.ident.span(),
+                            format!("\"{}\" is both the tag and a field of {}", tag, variant.ident),
+                        ))
+                    }
+                    _ => {}
+                }
+            }
+        }
+        _ => {}
+    }
+    for variant in &data.variants {
+        check_attrs(&variant.attrs, &mut errors);
+    }
+
+    if data
+        .variants
+        .iter()
+        .any(|variant| !variant.fields.is_empty())
+    {
+        for attr in input
+            .attrs
+            .iter()
+            .chain(data.variants.iter().flat_map(|variant| &variant.attrs))
+            .filter(|attr| attr.path().is_ident("parse"))
+        {
+            if let Ok(mode) = ParseMode::resolve(std::slice::from_ref(attr), &[]) {
+                if mode.numeric {
+                    errors.push(Error::new_spanned(
+                        &attr.meta,
+                        "`allow_numeric` can only be used on enums without fields",
+                    ));
+                }
+            }
+        }
+    }
+
+    if let Some(ident) = errors.check(get_default_variant(&input.attrs)).flatten() {
+        match data.variants.iter().find(|variant| variant.ident == ident) {
+            Some(variant) if !variant.fields.is_empty() => errors.push(Error::new(
+                ident.span(),
+                format!("Default variant {} can't have fields", ident),
+            )),
+            Some(_) => {}
+            None => errors.push(Error::new(
+                ident.span(),
+                format!("No variant {} in enum {}", ident, input.ident),
+            )),
+        }
+    }
+
+    errors.finish()
+}
+
+/// Checks that no string is accepted by more than one variant once casing, renames, aliases and parse modes are applied.
+pub fn check_collisions(accepted: &[(String, Span, Ident, CaseMode)]) -> syn::Result<()> {
+    let mut errors = Errors::default();
+
+    for (i, (spelling, span, variant, case)) in accepted.iter().enumerate() {
+        if let Some((_, _, other, _)) =
+            accepted[..i]
+                .iter()
+                .find(|(other, _, other_variant, other_case)| {
+                    let case = *case.max(other_case);
+                    other_variant != variant && case.fold(other) == case.fold(spelling)
+                })
+        {
+            errors.push(Error::new(
+                *span,
+                format!(
+                    "\"{}\" is accepted by both {} and {}",
+                    spelling, other, variant
+                ),
+            ));
+        }
+    }
+
+    errors.finish()
+}
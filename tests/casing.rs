@@ -1,6 +1,7 @@
 extern crate to_and_fro;
 
 #[cfg(test)]
+#[allow(deprecated)]
 mod tests {
 
     use std::str::FromStr;
@@ -84,4 +85,60 @@ mod tests {
             format!("{:?}", TestEnum2::LoremIpsum)
         );
     }
+
+    #[derive(ToAndFro)]
+    #[input_case("snake", "kebab")]
+    #[output_case("kebab")]
+    pub enum TestEnum3 {
+        LoremIpsum,
+        #[input_case("pascal", "snake", "upper")]
+        DolorSit,
+        Amet,
+    }
+
+    #[test]
+    pub fn multiple_input_casings() {
+        // Enum level casings
+        assert_eq!(
+            TestEnum3::from_str("lorem_ipsum").unwrap(),
+            TestEnum3::LoremIpsum
+        );
+        assert_eq!(
+            TestEnum3::from_str("lorem-ipsum").unwrap(),
+            TestEnum3::LoremIpsum
+        );
+        assert!(TestEnum3::from_str("LoremIpsum").is_err());
+
+        // Variant level casings replace the enum level ones
+        assert_eq!(
+            TestEnum3::from_str("DolorSit").unwrap(),
+            TestEnum3::DolorSit
+        );
+        assert_eq!(
+            TestEnum3::from_str("dolor_sit").unwrap(),
+            TestEnum3::DolorSit
+        );
+        assert_eq!(
+            TestEnum3::from_str("DOLORSIT").unwrap(),
+            TestEnum3::DolorSit
+        );
+        assert!(TestEnum3::from_str("dolor-sit").is_err());
+
+        // Casings producing the same string only produce one arm
+        assert_eq!(TestEnum3::from_str("amet").unwrap(), TestEnum3::Amet);
+        assert_eq!(
+            TestEnum3::from_str("nope").unwrap_err().expected(),
+            &[
+                "lorem_ipsum",
+                "lorem-ipsum",
+                "DolorSit",
+                "dolor_sit",
+                "DOLORSIT",
+                "amet"
+            ]
+        );
+
+        // Output is unaffected
+        assert_eq!(TestEnum3::DolorSit.to_string(), "dolor-sit");
+    }
 }
@@ -0,0 +1,131 @@
+extern crate to_and_fro;
+
+#[cfg(test)]
+mod tests {
+
+    use clap::{CommandFactory, Parser, ValueEnum};
+    use std::str::FromStr;
+    use to_and_fro::ToAndFro;
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "kebab", clap)]
+    pub enum Target {
+        /// Write to standard output.
+        Stdout,
+        /// Write to standard error.
+        #[to_and_fro(description = "Write to stderr, unbuffered.")]
+        Stderr,
+        /// Append to a file,
+        /// rotated daily.
+        #[to_and_fro(alias("log"))]
+        LogFile,
+        #[to_and_fro(reject)]
+        Internal,
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(input_case("snake", "kebab"), output_case = "kebab", clap)]
+    pub enum Output {
+        Stdout,
+        LogFile,
+    }
+
+    #[derive(Parser)]
+    struct Cli {
+        #[arg(long, value_enum)]
+        target: Option<Target>,
+        #[arg(long, value_enum)]
+        output: Option<Output>,
+    }
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(["cli"].iter().chain(args))
+    }
+
+    #[test]
+    pub fn parses_like_from_str() {
+        for input in [
+            "stdout", "log-file", "log", "internal", "Stdout", "LOG-FILE", "log_file", "",
+        ] {
+            assert_eq!(
+                parse(&["--target", input]).ok().and_then(|cli| cli.target),
+                <Target as FromStr>::from_str(input).ok(),
+                "{}",
+                input
+            );
+            assert_eq!(
+                parse(&["--output", input]).ok().and_then(|cli| cli.output),
+                <Output as FromStr>::from_str(input).ok(),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    pub fn input_case() {
+        // Listed by the Display spelling, with the other input spellings as aliases
+        let value = Output::LogFile.to_possible_value().unwrap();
+        assert_eq!(value.get_name(), Output::LogFile.to_string());
+        assert_eq!(
+            value.get_name_and_aliases().collect::<Vec<_>>(),
+            vec!["log-file", "log_file"]
+        );
+
+        for input in ["log-file", "log_file"] {
+            let cli = parse(&["--output", input]).unwrap();
+            assert_eq!(cli.output, Some(Output::LogFile));
+        }
+        assert!(parse(&["--output", "LogFile"]).is_err());
+
+        let help = Cli::command().render_long_help().to_string();
+        assert!(help.contains("log-file"));
+        assert!(!help.contains("log_file"));
+    }
+
+    #[test]
+    pub fn value_enum_from_str() {
+        assert_eq!(
+            <Target as ValueEnum>::from_str("log", false),
+            Ok(Target::LogFile)
+        );
+        assert!(<Target as ValueEnum>::from_str("internal", true).is_err());
+    }
+
+    #[test]
+    pub fn possible_values() {
+        let values = Target::value_variants()
+            .iter()
+            .filter_map(ValueEnum::to_possible_value)
+            .collect::<Vec<_>>();
+
+        assert_eq!(values.len(), 3);
+        assert!(Target::Internal.to_possible_value().unwrap().is_hide_set());
+        assert_eq!(values[0].get_name(), Target::Stdout.to_string());
+        assert_eq!(
+            values[0].get_help().map(ToString::to_string).as_deref(),
+            Some("Write to standard output.")
+        );
+        assert_eq!(
+            values[1].get_help().map(ToString::to_string).as_deref(),
+            Some("Write to stderr, unbuffered.")
+        );
+        assert_eq!(values[2].get_name(), "log-file");
+        assert_eq!(
+            values[2].get_name_and_aliases().collect::<Vec<_>>(),
+            vec!["log-file", "log"]
+        );
+        assert_eq!(
+            values[2].get_help().map(ToString::to_string).as_deref(),
+            Some("Append to a file, rotated daily.")
+        );
+    }
+
+    #[test]
+    pub fn help() {
+        let help = Cli::command().render_long_help().to_string();
+        assert!(help.contains("stdout"));
+        assert!(help.contains("log-file"));
+        assert!(!help.contains("internal"));
+    }
+}
@@ -0,0 +1,60 @@
+extern crate to_and_fro;
+
+#[cfg(test)]
+mod tests {
+
+    use to_and_fro::ToAndFro;
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "lower", parse(ignore_case))]
+    pub enum Level {
+        Debug,
+        #[to_and_fro(alias("information"))]
+        Info,
+        Warn,
+        #[to_and_fro(reject)]
+        Internal,
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "snake")]
+    pub enum Shape {
+        Point,
+        Circle(u32),
+    }
+
+    const DEFAULT_LEVEL: Level = match Level::parse_const("info") {
+        Some(level) => level,
+        None => panic!("unknown level"),
+    };
+
+    static LABELS: [&str; 2] = [Level::Debug.as_str(), Shape::Circle(0).as_str()];
+
+    struct Tagged<const N: usize>;
+
+    #[test]
+    pub fn in_const_contexts() {
+        assert_eq!(DEFAULT_LEVEL, Level::Info);
+        assert_eq!(LABELS, ["debug", "circle"]);
+
+        const WARN: usize = Level::Warn.as_str().len();
+        let _: Tagged<WARN> = Tagged::<4>;
+    }
+
+    #[test]
+    pub fn parse_const() {
+        assert_eq!(Level::parse_const("debug"), Some(Level::Debug));
+        assert_eq!(Level::parse_const("information"), Some(Level::Info));
+        assert_eq!(Level::parse_const("internal"), None);
+        assert_eq!(Level::Internal.as_str(), "internal");
+        assert_eq!(Level::parse_const(""), None);
+        assert_eq!(Level::parse_const("debugger"), None);
+    }
+
+    #[test]
+    pub fn exact_only() {
+        // ignore_case still applies to FromStr, but not to the byte for byte comparison
+        assert_eq!("DEBUG".parse::<Level>().unwrap(), Level::Debug);
+        assert_eq!(Level::parse_const("DEBUG"), None);
+    }
+}
@@ -1,6 +1,7 @@
 extern crate to_and_fro;
 
 #[cfg(test)]
+#[allow(deprecated)]
 mod tests {
 
     use std::str::FromStr;
@@ -29,6 +30,8 @@ mod tests {
 
     #[test]
     pub fn try_from_str() {
-        assert!(TestEnum::try_from("Load".to_string()).is_ok())
+        assert!(TestEnum::try_from("Load".to_string()).is_ok());
+        assert!(TestEnum::try_from("Load").is_ok());
+        assert!(TestEnum::parse(String::from("Load")).is_ok())
     }
 }
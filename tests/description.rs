@@ -0,0 +1,67 @@
+extern crate to_and_fro;
+
+#[cfg(test)]
+mod tests {
+
+    use to_and_fro::ToAndFro;
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "kebab")]
+    pub enum Status {
+        /// Waiting to be picked up.
+        Pending,
+        /// Being worked on,
+        /// possibly by several people.
+        InProgress,
+        /// Finished.
+        #[to_and_fro(description = "Completed and verified")]
+        Done,
+        Unknown,
+    }
+
+    #[derive(ToAndFro)]
+    pub enum Shape {
+        /// A circle of the given radius.
+        Circle(u32),
+        Empty,
+    }
+
+    const DONE: Option<&str> = Status::Done.description();
+
+    #[test]
+    pub fn description() {
+        assert_eq!(
+            Status::Pending.description(),
+            Some("Waiting to be picked up.")
+        );
+        assert_eq!(
+            Status::InProgress.description(),
+            Some("Being worked on, possibly by several people.")
+        );
+        assert_eq!(DONE, Some("Completed and verified"));
+        assert_eq!(Status::Unknown.description(), None);
+        assert_eq!(
+            Shape::Circle(2).description(),
+            Some("A circle of the given radius.")
+        );
+    }
+
+    #[test]
+    pub fn listing() {
+        let described = Status::iter()
+            .map(|status| (status.as_str(), status.description()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            Status::NAMES
+                .iter()
+                .copied()
+                .zip(Status::DESCRIPTIONS.iter().copied())
+                .collect::<Vec<_>>(),
+            described
+        );
+        assert_eq!(
+            Shape::DESCRIPTIONS,
+            &[Some("A circle of the given radius."), None]
+        );
+    }
+}
@@ -0,0 +1,69 @@
+extern crate to_and_fro;
+
+#[cfg(test)]
+mod tests {
+
+    use std::{convert::TryFrom, str::FromStr};
+    use to_and_fro::ToAndFro;
+
+    #[derive(ToAndFro)]
+    #[repr(u8)]
+    #[to_and_fro(casing = "snake", parse(allow_numeric))]
+    pub enum Opcode {
+        Push = 1,
+        Pop,
+        Jump = 7,
+        #[to_and_fro(reject)]
+        Halt = 255,
+    }
+
+    #[derive(ToAndFro)]
+    pub enum Signed {
+        Negative = -1,
+        Zero,
+        #[to_and_fro(parse(allow_numeric))]
+        Positive,
+    }
+
+    // Without a #[repr], no conversions are generated that this could clash with
+    impl From<Signed> for isize {
+        fn from(value: Signed) -> Self {
+            value as isize * 10
+        }
+    }
+
+    #[derive(ToAndFro)]
+    pub enum Shape {
+        Circle(u32),
+        Empty,
+    }
+
+    #[test]
+    pub fn index() {
+        assert_eq!(Opcode::Jump.index(), 2);
+        assert_eq!(Opcode::from_index(1), Some(Opcode::Pop));
+        assert_eq!(Opcode::from_index(4), None);
+        assert_eq!(Shape::Empty.index(), 1);
+        assert_eq!(Shape::Circle(3).index(), 0);
+    }
+
+    #[test]
+    pub fn repr() {
+        assert_eq!(Opcode::try_from(2u8), Ok(Opcode::Pop));
+        assert_eq!(Opcode::try_from(255u8), Ok(Opcode::Halt));
+        assert_eq!(Opcode::try_from(3u8).unwrap_err().input(), "3");
+        assert_eq!(u8::from(Opcode::Jump), 7);
+        assert_eq!(isize::from(Signed::Negative), -10);
+    }
+
+    #[test]
+    pub fn allow_numeric() {
+        assert_eq!(Opcode::from_str("7").unwrap(), Opcode::Jump);
+        assert_eq!(Opcode::from_str("pop").unwrap(), Opcode::Pop);
+        assert!(Opcode::from_str("255").is_err());
+        assert!(Opcode::from_str("3").is_err());
+
+        assert_eq!(Signed::from_str("1").unwrap(), Signed::Positive);
+        assert!(Signed::from_str("0").is_err());
+    }
+}
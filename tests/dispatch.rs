@@ -0,0 +1,87 @@
+extern crate to_and_fro;
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+    use to_and_fro::ToAndFro;
+
+    // Enough spellings to dispatch on length and first byte
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "kebab", parse(ignore_case))]
+    pub enum Element {
+        Hydrogen,
+        Helium,
+        Lithium,
+        Beryllium,
+        Boron,
+        Carbon,
+        Nitrogen,
+        Oxygen,
+        Fluorine,
+        Neon,
+        Sodium,
+        Magnesium,
+        Aluminium,
+        Silicon,
+        Phosphorus,
+        Sulfur,
+        Chlorine,
+        Argon,
+        Potassium,
+        Calcium,
+        Scandium,
+        Titanium,
+        Vanadium,
+        Chromium,
+        Manganese,
+        Iron,
+        Cobalt,
+        Nickel,
+        Copper,
+        Zinc,
+        #[to_and_fro(alias("wolfram"))]
+        Tungsten,
+        #[to_and_fro(rename = "éther")]
+        Ether,
+        #[to_and_fro(parse(prefix))]
+        Gallium,
+        #[to_and_fro(parse(prefix))]
+        Germanium,
+        Isotope(u8),
+        #[to_and_fro(reject)]
+        Unobtainium,
+    }
+
+    #[test]
+    pub fn exact() {
+        for name in Element::NAMES {
+            if let Ok(element) = Element::from_str(name) {
+                assert_eq!(element.as_str(), *name);
+            }
+        }
+        assert_eq!(Element::from_str("hydrogen").unwrap(), Element::Hydrogen);
+        assert_eq!(Element::from_str("zinc").unwrap(), Element::Zinc);
+        assert_eq!(Element::from_str("wolfram").unwrap(), Element::Tungsten);
+        assert_eq!(Element::from_str("éther").unwrap(), Element::Ether);
+    }
+
+    #[test]
+    pub fn fallbacks() {
+        assert_eq!(Element::from_str("NEON").unwrap(), Element::Neon);
+        assert_eq!(Element::from_str("gal").unwrap(), Element::Gallium);
+        assert_eq!(
+            Element::from_str("isotope(4)").unwrap(),
+            Element::Isotope(4)
+        );
+
+        let error = Element::from_str("g").unwrap_err();
+        assert_eq!(error.candidates(), &["gallium", "germanium"]);
+
+        assert_eq!(Element::Unobtainium.to_string(), "unobtainium");
+        let error = Element::from_str("unobtainium").unwrap_err();
+        assert!(error.candidates().is_empty());
+        assert!(Element::from_str("").is_err());
+        assert!(Element::from_str("neo").is_err());
+    }
+}
@@ -0,0 +1,72 @@
+extern crate to_and_fro;
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use to_and_fro::ToAndFro;
+
+    #[derive(ToAndFro)]
+    #[allow(dead_code)]
+    #[to_and_fro(input_case = "kebab")]
+    pub enum TestEnum {
+        HelloWorld,
+        #[to_and_fro(reject)]
+        FooBar,
+        BazQux,
+    }
+
+    #[test]
+    pub fn error_fields() {
+        let err = TestEnum::from_str("FooBar").unwrap_err();
+        assert_eq!(err.input(), "FooBar");
+        assert_eq!(err.enum_name(), "TestEnum");
+        assert_eq!(err.expected(), &["hello-world", "baz-qux"]);
+        assert_eq!(err.suggestion(), None);
+        assert_eq!(
+            err.to_string(),
+            "Invalid variant FooBar for enum TestEnum, expected one of: hello-world, baz-qux"
+        );
+    }
+
+    #[test]
+    pub fn suggestion() {
+        let err = TestEnum::from_str("helo-wrld").unwrap_err();
+        assert_eq!(err.suggestion(), Some("hello-world"));
+        assert_eq!(
+            err.to_string(),
+            "Invalid variant helo-wrld for enum TestEnum (did you mean hello-world?), expected one of: hello-world, baz-qux"
+        );
+
+        assert_eq!(
+            TestEnum::from_str("BAZ-QUZ").unwrap_err().suggestion(),
+            Some("baz-qux")
+        );
+        assert_eq!(TestEnum::from_str("qux").unwrap_err().suggestion(), None);
+    }
+
+    #[test]
+    pub fn try_from_error() {
+        let err = TestEnum::try_from("nope".to_string()).unwrap_err();
+        assert_eq!(err, TestEnumParseError::clone(&err));
+        assert_eq!(err.input(), "nope");
+    }
+
+    #[test]
+    pub fn full_input() {
+        // With std available the whole input is kept, and the error stays small enough for
+        // clippy::result_large_err
+        let long = "x".repeat(100);
+        assert_eq!(TestEnum::from_str(&long).unwrap_err().input(), long);
+        assert!(std::mem::size_of::<TestEnumParseError>() < 128);
+    }
+
+    #[test]
+    pub fn is_std_error() {
+        fn boxed(s: &str) -> Result<TestEnum, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(s.parse::<TestEnum>()?)
+        }
+
+        assert!(boxed("hello-world").is_ok());
+        assert!(boxed("HelloWorld").is_err());
+    }
+}
@@ -0,0 +1,86 @@
+extern crate to_and_fro;
+
+#[cfg(test)]
+mod tests {
+    use std::{fmt::Display, marker::PhantomData, str::FromStr};
+    use to_and_fro::ToAndFro;
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(serde)]
+    pub enum Level<T> {
+        A,
+        B,
+        #[to_and_fro(reject)]
+        Other(PhantomData<T>),
+    }
+
+    #[test]
+    pub fn phantom_parameter() {
+        assert_eq!(Level::<String>::from_str("A").unwrap(), Level::A);
+        assert!(Level::<String>::from_str("Other").is_err());
+        assert_eq!(Level::<u8>::Other(PhantomData).to_string(), "Other");
+        assert_eq!(serde_json::to_string(&Level::<u8>::B).unwrap(), r#""B""#);
+        assert_eq!(
+            serde_json::from_str::<Level<u8>>(r#""A""#).unwrap(),
+            Level::A
+        );
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "snake")]
+    pub enum Value<T, U>
+    where
+        U: Clone,
+    {
+        Single(T),
+        Pair(T, U),
+        Empty,
+    }
+
+    #[test]
+    pub fn inferred_bounds() {
+        assert_eq!(
+            Value::<u8, String>::from_str("pair(1,hi)").unwrap(),
+            Value::Pair(1, "hi".to_string())
+        );
+        assert_eq!(Value::<u8, String>::Single(3).to_string(), "single(3)");
+        assert_eq!(
+            Value::<u8, String>::try_from("empty").unwrap(),
+            Value::Empty
+        );
+    }
+
+    #[derive(ToAndFro)]
+    pub enum Borrowed<'a> {
+        #[to_and_fro(reject)]
+        Word(&'a str),
+        Nothing,
+    }
+
+    #[test]
+    pub fn lifetime_parameter() {
+        assert_eq!(Borrowed::Word("hi").to_string(), "Word(hi)");
+        assert_eq!(Borrowed::from_str("Nothing").unwrap(), Borrowed::Nothing);
+        assert_eq!(Borrowed::try_from("Nothing").unwrap().as_str(), "Nothing");
+    }
+
+    pub trait Unit:
+        FromStr + Display + Clone + PartialEq + Eq + std::hash::Hash + std::fmt::Debug
+    {
+    }
+    impl Unit for u32 {}
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(bound = "T: Unit")]
+    pub enum Custom<T> {
+        Amount(T),
+    }
+
+    #[test]
+    pub fn custom_bounds() {
+        assert_eq!(
+            Custom::<u32>::from_str("Amount(4)").unwrap(),
+            Custom::Amount(4)
+        );
+    }
+}
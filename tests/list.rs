@@ -0,0 +1,82 @@
+extern crate to_and_fro;
+
+#[cfg(test)]
+mod tests {
+
+    use to_and_fro::ToAndFro;
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "lower")]
+    pub enum Target {
+        Stdout,
+        File,
+        #[to_and_fro(alias("log"))]
+        Syslog,
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "lower", list(trim = false, duplicates = "reject"))]
+    pub enum Strict {
+        Read,
+        Write,
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(list(duplicates = "ignore"))]
+    pub enum Shape {
+        Circle(u32),
+        Empty,
+    }
+
+    #[test]
+    pub fn parse_list() {
+        assert_eq!(
+            Target::parse_list("stdout, file,log", ",").unwrap(),
+            vec![Target::Stdout, Target::File, Target::Syslog]
+        );
+        assert_eq!(
+            Target::parse_list("file,file", ",").unwrap(),
+            vec![Target::File, Target::File]
+        );
+        assert_eq!(Target::parse_list("  ", ",").unwrap(), vec![]);
+
+        let error = Target::parse_list("stdout, printer", ",").unwrap_err();
+        assert_eq!(error.element(), "printer");
+        assert_eq!(error.offset(), 8);
+        assert_eq!(error.error().unwrap().input(), "printer");
+        assert_eq!(
+            error.to_string(),
+            "At byte 8: Invalid variant printer for enum Target, expected one of: stdout, file, syslog, log"
+        );
+    }
+
+    #[test]
+    pub fn policies() {
+        let error = Strict::parse_list("read|write|read", "|").unwrap_err();
+        assert_eq!(error.element(), "read");
+        assert_eq!(error.offset(), 11);
+        assert!(error.error().is_none());
+
+        let error = Strict::parse_list("read| write", "|").unwrap_err();
+        assert_eq!(error.element(), " write");
+        assert_eq!(error.offset(), 5);
+
+        assert_eq!(
+            Shape::parse_list("Circle(1); Empty; Circle(2)", ";").unwrap(),
+            vec![Shape::Circle(1), Shape::Empty]
+        );
+    }
+
+    #[test]
+    pub fn join() {
+        assert_eq!(
+            Target::join(&[Target::Stdout, Target::Syslog], ", "),
+            "stdout, syslog"
+        );
+        assert_eq!(Target::join(&[], ", "), "");
+        assert_eq!(
+            Shape::join(&[Shape::Circle(3), Shape::Empty], ";"),
+            "Circle(3);Empty"
+        );
+    }
+}
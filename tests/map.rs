@@ -0,0 +1,97 @@
+extern crate to_and_fro;
+
+#[cfg(test)]
+mod tests {
+
+    use to_and_fro::ToAndFro;
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "kebab", map, serde)]
+    pub enum Status {
+        Pending,
+        InProgress,
+        Done,
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "kebab", map, serde)]
+    pub enum Stage {
+        Draft,
+        #[to_and_fro(reject)]
+        Legacy,
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(map)]
+    pub enum Level {
+        Low,
+        High,
+    }
+
+    #[test]
+    pub fn index() {
+        let mut counts = StatusMap::<u32>::default();
+        counts[Status::Done] += 2;
+        *counts.get_mut(&Status::Pending) += 1;
+
+        assert_eq!(counts[Status::Done], 2);
+        assert_eq!(*counts.get(&Status::Pending), 1);
+        assert_eq!(counts.values().sum::<u32>(), 3);
+    }
+
+    #[test]
+    pub fn from_fn() {
+        let names = LevelMap::from_fn(|level| level.to_string());
+        assert_eq!(names[Level::High], "High");
+        assert_eq!(
+            names.iter().collect::<Vec<_>>(),
+            vec![
+                (Level::Low, &"Low".to_string()),
+                (Level::High, &"High".to_string())
+            ]
+        );
+
+        let mut doubled = LevelMap::from_fn(|level| level.index());
+        for (_, value) in doubled.iter_mut() {
+            *value *= 2;
+        }
+        assert_eq!(doubled, LevelMap::from_fn(|level| level.index() * 2));
+    }
+
+    #[test]
+    pub fn serde() {
+        let map = StatusMap::from_fn(|status| status.index() as u8);
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(json, r#"{"pending":0,"in-progress":1,"done":2}"#);
+        assert_eq!(serde_json::from_str::<StatusMap<u8>>(&json).unwrap(), map);
+
+        let missing = serde_json::from_str::<StatusMap<u8>>(r#"{"pending":0,"done":2}"#);
+        assert!(missing
+            .unwrap_err()
+            .to_string()
+            .contains("missing field `in-progress`"));
+
+        let unknown = serde_json::from_str::<StatusMap<u8>>(r#"{"paused":0}"#);
+        assert!(unknown
+            .unwrap_err()
+            .to_string()
+            .contains("unknown variant `paused`"));
+
+        let duplicate = serde_json::from_str::<StatusMap<u8>>(
+            r#"{"pending":0,"in-progress":1,"done":2,"pending":3}"#,
+        );
+        assert!(duplicate
+            .unwrap_err()
+            .to_string()
+            .contains("duplicate field `pending`"));
+    }
+
+    #[test]
+    pub fn serde_rejected_key() {
+        // Keys are read by name, so a rejected variant's slot still round-trips
+        let map = StageMap::from_fn(|stage| stage.index() as u8);
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(json, r#"{"draft":0,"legacy":1}"#);
+        assert_eq!(serde_json::from_str::<StageMap<u8>>(&json).unwrap(), map);
+    }
+}
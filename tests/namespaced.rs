@@ -0,0 +1,84 @@
+extern crate to_and_fro;
+
+#[cfg(test)]
+mod tests {
+
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+    use to_and_fro::ToAndFro;
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "kebab", default = "Unknown", serde)]
+    pub enum Status {
+        InProgress,
+        #[to_and_fro(rename = "done", alias("finished"))]
+        Complete,
+        #[to_and_fro(reject)]
+        Unknown,
+    }
+
+    #[derive(ToAndFro, Serialize, Deserialize, Default)]
+    #[to_and_fro(output_case = "shouty_snake")]
+    #[serde(rename_all = "snake_case")]
+    pub enum Level {
+        #[default]
+        Low,
+        #[serde(rename = "hi")]
+        High,
+    }
+
+    mod glob {
+        // A glob import brings in nothing named `default` or `serde`, so std's and serde's
+        // attributes of those names still resolve
+        use serde::Deserialize;
+        use to_and_fro::*;
+
+        #[derive(ToAndFro, Deserialize, Default)]
+        #[to_and_fro(casing = "kebab")]
+        #[serde(rename_all = "UPPERCASE")]
+        pub enum Mode {
+            #[default]
+            Fast,
+            #[serde(alias = "s")]
+            Slow,
+        }
+    }
+
+    #[test]
+    pub fn namespaced() {
+        assert_eq!(Status::from_str("in-progress").unwrap(), Status::InProgress);
+        assert_eq!(Status::from_str("finished").unwrap(), Status::Complete);
+        assert_eq!(Status::from_str("unknown").unwrap(), Status::Unknown);
+        assert_eq!(Status::default(), Status::Unknown);
+        assert_eq!(Status::Complete.to_string(), "done");
+        assert_eq!(
+            serde_json::to_string(&Status::InProgress).unwrap(),
+            r#""in-progress""#
+        );
+    }
+
+    #[test]
+    pub fn glob_import() {
+        use glob::Mode;
+
+        assert_eq!(Mode::default(), Mode::Fast);
+        assert_eq!(Mode::Slow.to_string(), "slow");
+        assert_eq!(
+            serde_json::from_str::<Mode>(r#""FAST""#).unwrap(),
+            Mode::Fast
+        );
+        assert_eq!(serde_json::from_str::<Mode>(r#""s""#).unwrap(), Mode::Slow);
+    }
+
+    #[test]
+    pub fn alongside_serde_and_std() {
+        assert_eq!(Level::default(), Level::Low);
+        assert_eq!(Level::High.to_string(), "HIGH");
+        assert_eq!(Level::from_str("High").unwrap(), Level::High);
+        assert_eq!(serde_json::to_string(&Level::High).unwrap(), r#""hi""#);
+        assert_eq!(
+            serde_json::from_str::<Level>(r#""low""#).unwrap(),
+            Level::Low
+        );
+    }
+}
@@ -0,0 +1,58 @@
+#![no_std]
+
+use to_and_fro::ToAndFro;
+
+#[derive(ToAndFro)]
+#[to_and_fro(no_std, casing = "kebab", parse(ignore_case), set, map, serde)]
+pub enum Level {
+    Low,
+    #[to_and_fro(alias("mid"))]
+    Medium,
+    High,
+}
+
+#[derive(ToAndFro)]
+#[to_and_fro(no_std, casing = "lower", parse(any_case, prefix), serde)]
+pub enum Signal {
+    Start,
+    Stop,
+    Reset(u8),
+}
+
+#[derive(ToAndFro)]
+#[to_and_fro(no_std, casing = "snake", serde(tag = "kind", content = "value"))]
+pub enum Command {
+    Halt,
+    Jump(u16),
+    Blink { times: u8, on: bool },
+}
+
+#[derive(ToAndFro)]
+#[to_and_fro(no_std, casing = "snake", parse(allow_numeric))]
+#[repr(u8)]
+pub enum Mode {
+    Idle = 1,
+    Busy = 2,
+}
+
+/// `alloc` is only in scope here, so the enums above are checked against `core` alone.
+pub mod with_alloc {
+    extern crate alloc;
+
+    use to_and_fro::ToAndFro;
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(no_std, alloc, casing = "lower")]
+    pub enum Color {
+        Red,
+        Green,
+        Blue,
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(no_std, alloc, casing = "lower", serde(tag = "kind"))]
+    pub enum Event {
+        Start,
+        Stop { code: u8 },
+    }
+}
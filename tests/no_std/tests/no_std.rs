@@ -0,0 +1,106 @@
+extern crate to_and_fro_no_std;
+
+#[cfg(test)]
+mod tests {
+
+    use std::{rc::Rc, string::ToString};
+    use to_and_fro_no_std::{
+        with_alloc::{Color, Event},
+        Command, Level, LevelMap, LevelSet, Mode, Signal,
+    };
+
+    #[test]
+    pub fn round_trip() {
+        assert_eq!("medium".parse::<Level>().unwrap(), Level::Medium);
+        assert_eq!("MID".parse::<Level>().unwrap(), Level::Medium);
+        assert_eq!(Level::High.to_string(), "high");
+
+        assert_eq!("sta".parse::<Signal>().unwrap(), Signal::Start);
+        assert_eq!("reset(3)".parse::<Signal>().unwrap(), Signal::Reset(3));
+        assert_eq!(Signal::Reset(3).to_string(), "reset(3)");
+
+        assert_eq!("2".parse::<Mode>().unwrap(), Mode::Busy);
+        assert_eq!(Mode::try_from(1u8).unwrap(), Mode::Idle);
+        assert_eq!(Level::parse(Rc::<str>::from("low")).unwrap(), Level::Low);
+    }
+
+    #[test]
+    pub fn errors_without_allocating() {
+        let error = "stp".parse::<Signal>().unwrap_err();
+        assert_eq!(error.input(), "stp");
+        assert_eq!(error.suggestion(), Some("stop"));
+        assert_eq!(
+            error.to_string(),
+            "Invalid variant stp for enum Signal (did you mean stop?), expected one of: start, stop, reset"
+        );
+
+        let error = "st".parse::<Signal>().unwrap_err();
+        assert_eq!(error.candidates(), &["start", "stop"]);
+
+        let long = "x".repeat(100);
+        let error = long.parse::<Signal>().unwrap_err();
+        assert_eq!(error.input(), &long[..64]);
+
+        let error = "é".repeat(40).parse::<Signal>().unwrap_err();
+        assert_eq!(error.input(), "é".repeat(32));
+
+        let error = Mode::try_from(9u8).unwrap_err();
+        assert_eq!(error.input(), "9");
+        assert!(std::mem::size_of_val(&error) < 128);
+    }
+
+    #[test]
+    pub fn collections() {
+        let set = "low|high".parse::<LevelSet>().unwrap();
+        assert!(set.contains(&Level::Low) && !set.contains(&Level::Medium));
+        assert_eq!(set.to_string(), "low|high");
+
+        let map = LevelMap::from_fn(|level| level.index() * 10);
+        assert_eq!(map[Level::High], 20);
+    }
+
+    #[test]
+    pub fn serde() {
+        assert_eq!(serde_json::to_string(&Level::Low).unwrap(), "\"low\"");
+        assert_eq!(
+            serde_json::from_str::<Level>("\"mid\"").unwrap(),
+            Level::Medium
+        );
+        assert_eq!(
+            serde_json::to_string(&Signal::Reset(2)).unwrap(),
+            "\"reset(2)\""
+        );
+        assert_eq!(
+            serde_json::from_str::<Signal>("\"reset(2)\"").unwrap(),
+            Signal::Reset(2)
+        );
+
+        let map = serde_json::from_str::<LevelMap<u8>>(r#"{"low":1,"medium":2,"high":3}"#).unwrap();
+        assert_eq!(map[Level::Medium], 2);
+
+        let blink = r#"{"kind":"blink","value":{"times":3,"on":true}}"#;
+        let command = Command::Blink { times: 3, on: true };
+        assert_eq!(serde_json::to_string(&command).unwrap(), blink);
+        assert_eq!(serde_json::from_str::<Command>(blink).unwrap(), command);
+        // Without an allocator nothing can be buffered until the tag is read
+        let late =
+            serde_json::from_str::<Command>(r#"{"value":{"times":3,"on":true},"kind":"blink"}"#)
+                .unwrap_err();
+        assert!(late
+            .to_string()
+            .starts_with("expected `kind` before `value`"));
+    }
+
+    #[test]
+    pub fn alloc() {
+        assert_eq!(
+            Color::parse_list("red, blue", ",").unwrap(),
+            [Color::Red, Color::Blue]
+        );
+        assert_eq!(Color::join(&[Color::Green, Color::Red], "+"), "green+red");
+        assert_eq!(
+            serde_json::from_str::<Event>(r#"{"code":2,"kind":"stop"}"#).unwrap(),
+            Event::Stop { code: 2 }
+        );
+    }
+}
@@ -0,0 +1,81 @@
+extern crate to_and_fro;
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use to_and_fro::ToAndFro;
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(parse(any_case))]
+    #[to_and_fro(output_case = "kebab")]
+    pub enum TestEnum {
+        InProgress,
+        HttpServer,
+        #[to_and_fro(parse(exact))]
+        Done,
+        #[to_and_fro(parse(ignore_case))]
+        #[to_and_fro(rename = "Ünïcode")]
+        Unicode,
+        WithPayload(u32),
+    }
+
+    #[test]
+    pub fn any_case() {
+        for input in [
+            "InProgress",
+            "in_progress",
+            "in-progress",
+            "IN PROGRESS",
+            "In_Progress",
+            "inProgress",
+        ] {
+            assert_eq!(TestEnum::from_str(input).unwrap(), TestEnum::InProgress);
+        }
+
+        assert_eq!(
+            TestEnum::from_str("HTTPServer").unwrap(),
+            TestEnum::HttpServer
+        );
+        assert_eq!(
+            TestEnum::from_str("http-server").unwrap(),
+            TestEnum::HttpServer
+        );
+        assert!(TestEnum::from_str("inprogress").is_err());
+        assert!(TestEnum::from_str("in progress now").is_err());
+    }
+
+    #[test]
+    pub fn variant_overrides() {
+        assert_eq!(TestEnum::from_str("Done").unwrap(), TestEnum::Done);
+        assert!(TestEnum::from_str("done").is_err());
+
+        assert_eq!(TestEnum::from_str("üNÏCODE").unwrap(), TestEnum::Unicode);
+        assert!(TestEnum::from_str("unicode").is_err());
+    }
+
+    #[test]
+    pub fn payload_tag() {
+        assert_eq!(
+            TestEnum::from_str("with-payload(4)").unwrap(),
+            TestEnum::WithPayload(4)
+        );
+        assert_eq!(
+            TestEnum::from_str("WITH_PAYLOAD(4)").unwrap(),
+            TestEnum::WithPayload(4)
+        );
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(parse(ignore_case))]
+    pub enum TestEnum2 {
+        Alpha,
+        Beta,
+    }
+
+    #[test]
+    pub fn ignore_case() {
+        assert_eq!(TestEnum2::from_str("ALPHA").unwrap(), TestEnum2::Alpha);
+        assert_eq!(TestEnum2::from_str("beta").unwrap(), TestEnum2::Beta);
+        assert!(TestEnum2::from_str("b-eta").is_err());
+    }
+}
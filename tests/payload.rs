@@ -0,0 +1,142 @@
+extern crate to_and_fro;
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use to_and_fro::ToAndFro;
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "snake")]
+    pub enum TestEnum {
+        Empty,
+        Single(u32),
+        Pair(u8, String),
+        Named { x: String, y: i64 },
+    }
+
+    #[test]
+    pub fn display_payload() {
+        assert_eq!(TestEnum::Empty.to_string(), "empty");
+        assert_eq!(TestEnum::Single(42).to_string(), "single(42)");
+        assert_eq!(
+            TestEnum::Pair(1, "hi".to_string()).to_string(),
+            "pair(1,hi)"
+        );
+        assert_eq!(
+            TestEnum::Named {
+                x: "hi".to_string(),
+                y: -2
+            }
+            .to_string(),
+            "named{x=hi,y=-2}"
+        );
+    }
+
+    #[test]
+    pub fn as_str_is_tag() {
+        assert_eq!(TestEnum::Single(42).as_str(), "single");
+        assert_eq!(TestEnum::Pair(1, "hi".to_string()).as_str(), "pair");
+    }
+
+    #[test]
+    pub fn parse_payload() {
+        assert_eq!(TestEnum::from_str("empty").unwrap(), TestEnum::Empty);
+        assert_eq!(
+            TestEnum::from_str("single(42)").unwrap(),
+            TestEnum::Single(42)
+        );
+        assert_eq!(
+            TestEnum::from_str("pair(1,hi,there)").unwrap(),
+            TestEnum::Pair(1, "hi,there".to_string())
+        );
+        assert_eq!(
+            TestEnum::from_str("named{x=hi,y=-2}").unwrap(),
+            TestEnum::Named {
+                x: "hi".to_string(),
+                y: -2
+            }
+        );
+    }
+
+    #[test]
+    pub fn reject_bad_payload() {
+        assert!(TestEnum::from_str("single").is_err());
+        assert!(TestEnum::from_str("single(x)").is_err());
+        assert!(TestEnum::from_str("single(42").is_err());
+        assert!(TestEnum::from_str("named{y=-2,x=hi}").is_err());
+        assert!(TestEnum::from_str("pair(1)").is_err());
+
+        // Once the tag and delimiters match, the error names the field that didn't parse
+        let error = TestEnum::from_str("single(x)").unwrap_err();
+        assert_eq!(error.field(), Some("0"));
+        assert_eq!(
+            error.to_string(),
+            "Invalid payload single(x) for enum TestEnum, field 0 couldn't be parsed"
+        );
+        assert_eq!(
+            TestEnum::from_str("pair(1)").unwrap_err().field(),
+            Some("1")
+        );
+        assert_eq!(TestEnum::from_str("nope").unwrap_err().field(), None);
+    }
+
+    #[test]
+    pub fn delimiters_in_fields() {
+        // Nothing is escaped: the last field can hold the separator, and any field the other
+        // delimiters, since only the separators between fields are split on
+        for value in [
+            TestEnum::Pair(1, "a,b".to_string()),
+            TestEnum::Named {
+                x: "(a}=b".to_string(),
+                y: 1,
+            },
+        ] {
+            assert_eq!(TestEnum::from_str(&value.to_string()).unwrap(), value);
+        }
+
+        // A separator in any other field can't be read back, and is reported as such
+        let value = TestEnum::Named {
+            x: "a,b".to_string(),
+            y: 1,
+        };
+        assert_eq!(value.to_string(), "named{x=a,b,y=1}");
+        let error = TestEnum::from_str(&value.to_string()).unwrap_err();
+        assert_eq!(error.field(), Some("y"));
+    }
+
+    #[test]
+    pub fn structural_eq_and_debug() {
+        assert_ne!(TestEnum::Single(1), TestEnum::Single(2));
+        assert_eq!(TestEnum::Single(1).clone(), TestEnum::Single(1));
+        assert_eq!(
+            format!("{:?}", TestEnum::Pair(1, "hi".to_string())),
+            r#"TestEnum::Pair(1, "hi")"#
+        );
+        assert_eq!(format!("{:?}", TestEnum::Empty), "TestEnum::Empty");
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(payload(open = "[", close = "]", separator = ";"))]
+    pub enum TestEnum2 {
+        Point(i32, i32),
+        #[to_and_fro(payload(open = "<", close = ">", assign = ":"))]
+        Size {
+            w: u32,
+            h: u32,
+        },
+    }
+
+    #[test]
+    pub fn custom_syntax() {
+        assert_eq!(TestEnum2::Point(1, -1).to_string(), "Point[1;-1]");
+        assert_eq!(TestEnum2::Size { w: 3, h: 4 }.to_string(), "Size<w:3;h:4>");
+        assert_eq!(
+            TestEnum2::from_str("Point[1;-1]").unwrap(),
+            TestEnum2::Point(1, -1)
+        );
+        assert_eq!(
+            TestEnum2::from_str("Size<w:3;h:4>").unwrap(),
+            TestEnum2::Size { w: 3, h: 4 }
+        );
+    }
+}
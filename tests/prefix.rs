@@ -0,0 +1,86 @@
+extern crate to_and_fro;
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+    use to_and_fro::ToAndFro;
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "lower", parse(prefix))]
+    pub enum Command {
+        Status,
+        Start,
+        #[to_and_fro(alias("setup"))]
+        Configure,
+        Stop,
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "lower")]
+    pub enum Partial {
+        #[to_and_fro(parse(prefix))]
+        Delete,
+        Describe,
+        D,
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "lower")]
+    pub enum Mixed {
+        #[to_and_fro(parse(prefix))]
+        Status,
+        Stop,
+        #[to_and_fro(rename = "STATS", parse(ignore_case))]
+        Stats,
+    }
+
+    #[test]
+    pub fn unique() {
+        assert_eq!(Command::from_str("stat").unwrap(), Command::Status);
+        assert_eq!(Command::from_str("star").unwrap(), Command::Start);
+        assert_eq!(Command::from_str("sto").unwrap(), Command::Stop);
+        assert_eq!(Command::from_str("c").unwrap(), Command::Configure);
+        assert_eq!(Command::from_str("conf").unwrap(), Command::Configure);
+        assert_eq!(Command::from_str("se").unwrap(), Command::Configure);
+        assert_eq!(Command::from_str("status").unwrap(), Command::Status);
+        assert!(Command::from_str("statuses").is_err());
+        assert!(Command::from_str("").is_err());
+    }
+
+    #[test]
+    pub fn ambiguous() {
+        let error = Command::from_str("sta").unwrap_err();
+        assert_eq!(error.candidates(), &["status", "start"]);
+        assert_eq!(
+            error.to_string(),
+            "Ambiguous variant sta for enum Command, could be any of: status, start"
+        );
+
+        let error = Command::from_str("s").unwrap_err();
+        assert_eq!(error.candidates(), &["status", "start", "setup", "stop"]);
+        assert!(Command::from_str("x").unwrap_err().candidates().is_empty());
+    }
+
+    #[test]
+    pub fn per_variant() {
+        assert_eq!(Partial::from_str("del").unwrap(), Partial::Delete);
+        // Describe doesn't take prefixes, but still makes "de" ambiguous
+        let error = Partial::from_str("de").unwrap_err();
+        assert_eq!(error.candidates(), &["delete", "describe"]);
+        assert_eq!(Partial::from_str("d").unwrap(), Partial::D);
+        assert!(Partial::from_str("desc").is_err());
+    }
+
+    #[test]
+    pub fn mixed_modes() {
+        // Ambiguity is checked against every spelling, under each variant's own case mode
+        let error = Mixed::from_str("st").unwrap_err();
+        assert_eq!(error.candidates(), &["status", "stop", "STATS"]);
+        let error = Mixed::from_str("stat").unwrap_err();
+        assert_eq!(error.candidates(), &["status", "STATS"]);
+        assert_eq!(Mixed::from_str("statu").unwrap(), Mixed::Status);
+        assert_eq!(Mixed::from_str("stats").unwrap(), Mixed::Stats);
+        assert!(Mixed::from_str("sto").is_err());
+    }
+}
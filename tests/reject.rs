@@ -1,6 +1,7 @@
 extern crate to_and_fro;
 
 #[cfg(test)]
+#[allow(deprecated)]
 mod tests {
     use std::str::FromStr;
     use to_and_fro::ToAndFro;
@@ -0,0 +1,65 @@
+extern crate to_and_fro;
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use to_and_fro::ToAndFro;
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "snake")]
+    pub enum TestEnum {
+        #[to_and_fro(rename = "in-progress")]
+        #[to_and_fro(alias("wip", "started"))]
+        InProgress,
+        #[to_and_fro(alias = "finished")]
+        Done,
+        #[to_and_fro(rename = "Payload")]
+        #[to_and_fro(alias = "data")]
+        WithPayload(u32),
+    }
+
+    #[test]
+    pub fn rename_both_ways() {
+        assert_eq!(TestEnum::InProgress.to_string(), "in-progress");
+        assert_eq!(
+            TestEnum::from_str("in-progress").unwrap(),
+            TestEnum::InProgress
+        );
+        assert!(TestEnum::from_str("in_progress").is_err());
+    }
+
+    #[test]
+    pub fn aliases_parse_only() {
+        assert_eq!(TestEnum::from_str("wip").unwrap(), TestEnum::InProgress);
+        assert_eq!(TestEnum::from_str("started").unwrap(), TestEnum::InProgress);
+        assert_eq!(TestEnum::from_str("finished").unwrap(), TestEnum::Done);
+        assert_eq!(TestEnum::from_str("done").unwrap(), TestEnum::Done);
+        assert_eq!(TestEnum::Done.as_str(), "done");
+    }
+
+    #[test]
+    pub fn payload_aliases() {
+        assert_eq!(TestEnum::WithPayload(3).to_string(), "Payload(3)");
+        assert_eq!(
+            TestEnum::from_str("data(3)").unwrap(),
+            TestEnum::WithPayload(3)
+        );
+    }
+
+    #[test]
+    pub fn expected_includes_aliases() {
+        let err = TestEnum::from_str("nope").unwrap_err();
+        assert_eq!(
+            err.expected(),
+            &[
+                "in-progress",
+                "wip",
+                "started",
+                "done",
+                "finished",
+                "Payload",
+                "data"
+            ]
+        );
+    }
+}
@@ -0,0 +1,199 @@
+extern crate to_and_fro;
+
+#[cfg(test)]
+mod tests {
+
+    use serde::{de::DeserializeOwned, Deserialize, Serialize};
+    use std::fmt::Debug;
+    use to_and_fro::ToAndFro;
+
+    #[derive(ToAndFro)]
+    #[repr(u8)]
+    #[to_and_fro(casing = "lower", serde)]
+    pub enum Spelled {
+        Low = 1,
+        High = 5,
+    }
+
+    #[derive(ToAndFro)]
+    #[repr(u8)]
+    #[to_and_fro(casing = "lower", serde = "index", map)]
+    pub enum Indexed {
+        Low = 1,
+        High = 5,
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "lower", serde = "index")]
+    pub enum Unsized {
+        Below = -3,
+        Above = 7,
+    }
+
+    #[derive(ToAndFro)]
+    #[repr(i16)]
+    #[to_and_fro(casing = "lower", serde = "unit_variant")]
+    pub enum Tagged {
+        Low = -1,
+        High = 5,
+    }
+
+    #[derive(ToAndFro)]
+    #[repr(u8)]
+    #[to_and_fro(casing = "lower", serde = "auto")]
+    pub enum Auto {
+        Low = 1,
+        High = 5,
+    }
+
+    #[derive(ToAndFro)]
+    #[repr(u8)]
+    #[to_and_fro(casing = "lower", serde, parse(allow_numeric))]
+    pub enum Numeric {
+        Low = 1,
+        High = 5,
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "lower", serde, parse(allow_numeric))]
+    pub enum Status {
+        Active,
+        #[to_and_fro(reject)]
+        Internal,
+    }
+
+    #[derive(ToAndFro)]
+    #[repr(u8)]
+    #[to_and_fro(casing = "lower", serde = "index")]
+    pub enum IndexedStatus {
+        Active,
+        #[to_and_fro(reject)]
+        Internal,
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "lower", serde = "unit_variant")]
+    pub enum TaggedStatus {
+        Active,
+        #[to_and_fro(reject)]
+        Internal,
+    }
+
+    fn round_trip<T>(value: T, json: &str, binary: &[u8])
+    where
+        T: Serialize + DeserializeOwned + PartialEq + Debug,
+    {
+        assert_eq!(serde_json::to_string(&value).unwrap(), json);
+        assert_eq!(serde_json::from_str::<T>(json).unwrap(), value);
+
+        let bytes = postcard::to_stdvec(&value).unwrap();
+        assert_eq!(bytes, binary);
+        assert_eq!(postcard::from_bytes::<T>(&bytes).unwrap(), value);
+    }
+
+    #[test]
+    pub fn string() {
+        round_trip(Spelled::High, r#""high""#, b"\x04high");
+        // Integers are only read for variants opted in with allow_numeric
+        assert!(serde_json::from_str::<Spelled>("5").is_err());
+        assert_eq!(serde_json::from_str::<Numeric>("5").unwrap(), Numeric::High);
+        assert_eq!(
+            serde_json::from_str::<Numeric>(r#""5""#).unwrap(),
+            Numeric::High
+        );
+    }
+
+    #[test]
+    pub fn index() {
+        round_trip(Indexed::High, "5", &[5]);
+        round_trip(Indexed::Low, "1", &[1]);
+        assert!(serde_json::from_str::<Indexed>("2").is_err());
+        assert!(postcard::from_bytes::<Indexed>(&[0]).is_err());
+
+        let map = IndexedMap::from_fn(|key| key.as_str().len());
+        let json = serde_json::to_string(&map).unwrap();
+        // Map keys are always written by name
+        assert_eq!(json, r#"{"low":3,"high":4}"#);
+        assert_eq!(
+            serde_json::from_str::<IndexedMap<usize>>(&json).unwrap(),
+            map
+        );
+    }
+
+    #[test]
+    pub fn index_without_repr() {
+        // Without a #[repr] the discriminant is an isize, written as an i64
+        round_trip(Unsized::Below, "-3", &[5]);
+        round_trip(Unsized::Above, "7", &[14]);
+        assert!(serde_json::from_str::<Unsized>("0").is_err());
+    }
+
+    #[test]
+    pub fn unit_variant() {
+        // postcard tags a unit variant with its position, not its discriminant
+        round_trip(Tagged::Low, r#""low""#, &[0]);
+        round_trip(Tagged::High, r#""high""#, &[1]);
+        assert!(postcard::from_bytes::<Tagged>(&[2]).is_err());
+
+        let error = serde_json::from_str::<Tagged>(r#""mid""#).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("unknown variant `mid`, expected `low` or `high`"));
+    }
+
+    #[test]
+    pub fn auto() {
+        round_trip(Auto::High, r#""high""#, &[5]);
+        round_trip(Auto::Low, r#""low""#, &[1]);
+        assert_eq!(serde_json::from_str::<Auto>("5").unwrap(), Auto::High);
+    }
+
+    #[test]
+    pub fn rejected() {
+        // Rejected variants are written, but never read back through any representation
+        assert_eq!(
+            serde_json::to_string(&Status::Internal).unwrap(),
+            r#""internal""#
+        );
+        assert!(serde_json::from_str::<Status>(r#""internal""#).is_err());
+        assert!(serde_json::from_str::<Status>("1").is_err());
+        assert_eq!(serde_json::from_str::<Status>("0").unwrap(), Status::Active);
+
+        assert_eq!(
+            serde_json::to_string(&IndexedStatus::Internal).unwrap(),
+            "1"
+        );
+        assert!(serde_json::from_str::<IndexedStatus>("1").is_err());
+        assert!(postcard::from_bytes::<IndexedStatus>(&[1]).is_err());
+        assert_eq!(
+            postcard::from_bytes::<IndexedStatus>(&[0]).unwrap(),
+            IndexedStatus::Active
+        );
+
+        let bytes = postcard::to_stdvec(&TaggedStatus::Internal).unwrap();
+        assert_eq!(bytes, [1]);
+        assert!(postcard::from_bytes::<TaggedStatus>(&bytes).is_err());
+        assert!(serde_json::from_str::<TaggedStatus>(r#""internal""#).is_err());
+    }
+
+    #[test]
+    pub fn in_structs() {
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct Record {
+            indexed: Indexed,
+            tagged: Tagged,
+            auto: Auto,
+        }
+
+        let record = Record {
+            indexed: Indexed::High,
+            tagged: Tagged::High,
+            auto: Auto::Low,
+        };
+        round_trip(
+            record,
+            r#"{"indexed":5,"tagged":"high","auto":"low"}"#,
+            &[5, 1, 1],
+        );
+    }
+}
@@ -1,10 +1,10 @@
 extern crate to_and_fro;
 
 #[cfg(test)]
+#[allow(deprecated)]
 mod tests {
 
     use serde::{Deserialize, Serialize};
-    use std::str::FromStr;
     use to_and_fro::*;
 
     #[derive(ToAndFro)]
@@ -34,4 +34,83 @@ mod tests {
         let v: Container = serde_json::from_str(r#"{"value":"foo-bar"}"#).unwrap();
         assert_eq!(v.value, TestEnum::FooBar)
     }
+
+    #[derive(ToAndFro)]
+    #[repr(u8)]
+    #[to_and_fro(casing = "lower", serde, parse(allow_numeric))]
+    pub enum Level {
+        Low = 1,
+        High = 5,
+        #[to_and_fro(parse(prefix))]
+        Medium,
+        #[to_and_fro(parse(prefix))]
+        Maximum,
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "lower", serde)]
+    pub enum Reading {
+        Idle,
+        Value(u32),
+    }
+
+    #[derive(Deserialize)]
+    struct Borrowed<'a> {
+        #[serde(borrow)]
+        name: &'a str,
+        level: Level,
+    }
+
+    #[test]
+    pub fn deserialize_without_allocating() {
+        let v: Borrowed = serde_json::from_str(r#"{"name":"x","level":"high"}"#).unwrap();
+        assert_eq!((v.name, v.level), ("x", Level::High));
+
+        // Escapes can't be borrowed, going through visit_str instead
+        let v: Level = serde_json::from_str(r#""hi\u0067h""#).unwrap();
+        assert_eq!(v, Level::High);
+        let v: Level = serde_json::from_reader(&br#""low""#[..]).unwrap();
+        assert_eq!(v, Level::Low);
+        let v: Reading = serde_json::from_str(r#""value(7)""#).unwrap();
+        assert_eq!(v, Reading::Value(7));
+    }
+
+    #[test]
+    pub fn deserialize_bytes_and_discriminants() {
+        use serde::de::{value::Error, IntoDeserializer};
+
+        let bytes = serde::de::value::BytesDeserializer::<Error>::new(b"medium");
+        assert_eq!(Level::deserialize(bytes).unwrap(), Level::Medium);
+        let bytes = serde::de::value::BytesDeserializer::<Error>::new(b"\xff");
+        assert!(Level::deserialize(bytes).is_err());
+
+        assert_eq!(serde_json::from_str::<Level>("5").unwrap(), Level::High);
+        assert_eq!(serde_json::from_str::<Level>("6").unwrap(), Level::Medium);
+        let error = serde_json::from_str::<Level>("9").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("invalid value: integer `9`, expected a Level variant"));
+        assert!(serde_json::from_str::<Level>("-1").is_err());
+        assert_eq!(
+            Level::deserialize(IntoDeserializer::<Error>::into_deserializer(1u64)).unwrap(),
+            Level::Low
+        );
+        let error: Result<Reading, Error> =
+            Reading::deserialize(IntoDeserializer::<Error>::into_deserializer(0u64));
+        assert!(error.is_err());
+    }
+
+    #[test]
+    pub fn unknown_variant() {
+        let error = serde_json::from_str::<Level>(r#""lo""#).unwrap_err();
+        assert!(error.to_string().starts_with(
+            "unknown variant `lo`, expected one of `low`, `high`, `medium`, `maximum`"
+        ));
+
+        // Ambiguous prefixes keep the candidates they could stand for
+        let error = serde_json::from_str::<Level>(r#""m""#).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Ambiguous variant m for enum Level"));
+    }
 }
@@ -0,0 +1,86 @@
+extern crate to_and_fro;
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+    use to_and_fro::ToAndFro;
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "lower", set)]
+    pub enum Permission {
+        Read,
+        Write,
+        #[to_and_fro(alias("exec"))]
+        Execute,
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "kebab", set(separator = ","), list(trim = false))]
+    pub enum Capability {
+        NetworkAccess,
+        FileSystem,
+    }
+
+    #[test]
+    pub fn operations() {
+        let mut set = PermissionSet::new();
+        assert!(set.is_empty());
+        assert!(set.insert(Permission::Read));
+        assert!(!set.insert(Permission::Read));
+        assert!(set.insert(Permission::Execute));
+        assert!(set.contains(&Permission::Execute));
+        assert!(!set.contains(&Permission::Write));
+        assert_eq!(set.len(), 2);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![Permission::Read, Permission::Execute]
+        );
+
+        assert!(set.remove(&Permission::Read));
+        assert!(!set.remove(&Permission::Read));
+        assert_eq!(PermissionSet::all().len(), Permission::COUNT);
+
+        let read_write = [Permission::Read, Permission::Write]
+            .into_iter()
+            .collect::<PermissionSet>();
+        assert_eq!(read_write.union(set), PermissionSet::all());
+        assert_eq!(read_write.intersection(set), PermissionSet::new());
+        assert_eq!(PermissionSet::all().difference(set), read_write);
+    }
+
+    #[test]
+    pub fn display() {
+        let set = PermissionSet::from_str("write|read").unwrap();
+        assert_eq!(set.to_string(), "read|write");
+        assert_eq!(PermissionSet::new().to_string(), "");
+        assert_eq!(
+            CapabilitySet::all().to_string(),
+            "network-access,file-system"
+        );
+    }
+
+    #[test]
+    pub fn from_str() {
+        assert_eq!(
+            PermissionSet::from_str("exec").unwrap(),
+            [Permission::Execute].into_iter().collect()
+        );
+        assert_eq!(PermissionSet::from_str("").unwrap(), PermissionSet::new());
+        assert_eq!(PermissionSet::from_str("  ").unwrap(), PermissionSet::new());
+        assert_eq!(
+            PermissionSet::from_str("read | write").unwrap(),
+            PermissionSet::from_str("read|write").unwrap()
+        );
+        assert_eq!(
+            CapabilitySet::from_str("file-system, network-access")
+                .unwrap_err()
+                .input(),
+            " network-access"
+        );
+        assert_eq!(
+            PermissionSet::from_str("read|delete").unwrap_err().input(),
+            "delete"
+        );
+    }
+}
@@ -0,0 +1,54 @@
+extern crate to_and_fro;
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use to_and_fro::ToAndFro;
+
+    #[derive(ToAndFro, Debug, Clone, Copy, PartialEq)]
+    #[to_and_fro(skip(Debug, Clone, Copy, PartialEq))]
+    pub enum TestEnum {
+        Generation,
+        Load,
+    }
+
+    #[test]
+    pub fn std_derives() {
+        assert_eq!(format!("{:?}", TestEnum::Load), "Load");
+        assert_eq!(TestEnum::from_str("Load").unwrap(), TestEnum::Load);
+        assert_eq!(TestEnum::Generation.to_string(), "Generation");
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(impls(Clone, PartialEq, Debug))]
+    pub enum TestEnum2 {
+        Measure(f64),
+        Empty,
+    }
+
+    #[test]
+    pub fn opt_in() {
+        assert_eq!(
+            TestEnum2::from_str("Measure(1.5)").unwrap(),
+            TestEnum2::Measure(1.5)
+        );
+        assert_eq!(TestEnum2::Empty.clone(), TestEnum2::Empty);
+        assert_eq!(
+            format!("{:?}", TestEnum2::Measure(2.0)),
+            "TestEnum2::Measure(2.0)"
+        );
+    }
+
+    #[derive(ToAndFro, Debug)]
+    #[to_and_fro(skip(Debug))]
+    pub enum TestEnum3 {
+        Name(String),
+    }
+
+    #[test]
+    pub fn non_copy_fields() {
+        let value = TestEnum3::from_str("Name(hi)").unwrap();
+        assert_eq!(value.clone(), TestEnum3::Name("hi".to_string()));
+        assert_eq!(format!("{:?}", value), r#"Name("hi")"#);
+    }
+}
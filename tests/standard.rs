@@ -66,4 +66,25 @@ mod tests {
             assert!(ensure_eq(i, i));
         }
     }
+
+    #[test]
+    pub fn test_parse() {
+        use std::{borrow::Cow, rc::Rc, sync::Arc};
+
+        let name = String::from("Load");
+        assert_eq!(TestEnum::parse(&name), Ok(TestEnum::Load));
+        assert_eq!(TestEnum::parse(name), Ok(TestEnum::Load));
+        assert_eq!(
+            TestEnum::parse(Box::<str>::from("Load")),
+            Ok(TestEnum::Load)
+        );
+        assert_eq!(TestEnum::parse(Rc::<str>::from("Load")), Ok(TestEnum::Load));
+        assert_eq!(
+            TestEnum::parse(Arc::<str>::from("Load")),
+            Ok(TestEnum::Load)
+        );
+        assert_eq!(TestEnum::parse(Cow::Borrowed("Load")), Ok(TestEnum::Load));
+        assert_eq!(TestEnum::try_from("Load"), Ok(TestEnum::Load));
+        assert!(TestEnum::parse("Unload").is_err());
+    }
 }
@@ -0,0 +1,315 @@
+extern crate to_and_fro;
+
+#[cfg(test)]
+mod tests {
+
+    use serde::{
+        de::{value, DeserializeOwned, IntoDeserializer, Visitor},
+        Deserialize, Deserializer, Serialize,
+    };
+    use std::{fmt::Debug, marker::PhantomData, str::FromStr};
+    use to_and_fro::ToAndFro;
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "kebab", serde = "external")]
+    pub enum External {
+        PowerOff,
+        #[to_and_fro(rename = "go-to", alias = "goto")]
+        GoTo(u8),
+        Move(i8, i8),
+        SetLight {
+            r#type: u8,
+            on: bool,
+        },
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "snake", serde(tag = "type"))]
+    pub enum Internal {
+        PowerOff,
+        SetLight { level: u8, on: bool },
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "snake", serde(tag = "t", content = "c"))]
+    pub enum Adjacent {
+        PowerOff,
+        GoTo(u8),
+        Move(i8, i8),
+        SetLight { level: u8, on: bool },
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "lower", serde(tag = "t", content = "c"))]
+    pub enum Generic<T> {
+        Value(T),
+        Marker(PhantomData<T>),
+        #[to_and_fro(reject)]
+        Hidden(T),
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "snake", parse(ignore_case, prefix), serde(tag = "kind"))]
+    pub enum Relaxed {
+        Halt,
+        Resume,
+        Restart,
+        #[to_and_fro(parse(any_case))]
+        SetSpeed {
+            speed: u8,
+        },
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(serde(tag = "t", content = "c"), parse(allow_numeric))]
+    pub enum Numeric {
+        Zero = 0,
+        Seven = 7,
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "snake", serde(tag = "type"))]
+    pub enum Wide {
+        Signed { value: i128 },
+        Unsigned { value: u128 },
+    }
+
+    /// A self-describing value that hands out 128-bit integers, which serde_json never does.
+    enum Value {
+        Str(&'static str),
+        I128(i128),
+        U128(u128),
+    }
+
+    impl<'de> IntoDeserializer<'de> for Value {
+        type Deserializer = Self;
+
+        fn into_deserializer(self) -> Self {
+            self
+        }
+    }
+
+    impl<'de> Deserializer<'de> for Value {
+        type Error = value::Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, value::Error> {
+            match self {
+                Value::Str(v) => visitor.visit_borrowed_str(v),
+                Value::I128(v) => visitor.visit_i128(v),
+                Value::U128(v) => visitor.visit_u128(v),
+            }
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map struct enum identifier ignored_any
+        }
+    }
+
+    fn json<T>(value: &T, json: &str)
+    where
+        T: Serialize + DeserializeOwned + PartialEq + Debug,
+    {
+        assert_eq!(serde_json::to_string(value).unwrap(), json);
+        assert_eq!(&serde_json::from_str::<T>(json).unwrap(), value);
+    }
+
+    fn round_trip<T>(value: T, json: &str)
+    where
+        T: Serialize + DeserializeOwned + PartialEq + Debug,
+    {
+        self::json(&value, json);
+
+        let bytes = postcard::to_stdvec(&value).unwrap();
+        assert_eq!(postcard::from_bytes::<T>(&bytes).unwrap(), value);
+    }
+
+    #[test]
+    pub fn external() {
+        round_trip(External::PowerOff, r#""power-off""#);
+        round_trip(External::GoTo(3), r#"{"go-to":3}"#);
+        round_trip(External::Move(-1, 2), r#"{"move":[-1,2]}"#);
+        round_trip(
+            External::SetLight {
+                r#type: 2,
+                on: true,
+            },
+            r#"{"set-light":{"type":2,"on":true}}"#,
+        );
+        // The tag is the variant's `as_str` spelling
+        assert_eq!(External::GoTo(3).as_str(), "go-to");
+        // Any spelling `FromStr` accepts is read as the tag
+        assert_eq!(
+            serde_json::from_str::<External>(r#"{"goto":3}"#).unwrap(),
+            External::GoTo(3)
+        );
+    }
+
+    #[test]
+    pub fn internal() {
+        // Like serde_derive's, internally tagged enums need a self-describing format
+        json(&Internal::PowerOff, r#"{"type":"power_off"}"#);
+        json(
+            &Internal::SetLight {
+                level: 4,
+                on: false,
+            },
+            r#"{"type":"set_light","level":4,"on":false}"#,
+        );
+        assert_eq!(
+            serde_json::from_str::<Internal>(
+                r#"{"type":"set_light","on":true,"extra":1,"level":2}"#
+            )
+            .unwrap(),
+            Internal::SetLight { level: 2, on: true }
+        );
+    }
+
+    #[test]
+    pub fn adjacent() {
+        round_trip(Adjacent::PowerOff, r#"{"t":"power_off"}"#);
+        round_trip(Adjacent::GoTo(7), r#"{"t":"go_to","c":7}"#);
+        round_trip(Adjacent::Move(1, -2), r#"{"t":"move","c":[1,-2]}"#);
+        round_trip(
+            Adjacent::SetLight { level: 9, on: true },
+            r#"{"t":"set_light","c":{"level":9,"on":true}}"#,
+        );
+        assert_eq!(
+            serde_json::from_str::<Adjacent>(r#"{"t":"power_off","c":null}"#).unwrap(),
+            Adjacent::PowerOff
+        );
+    }
+
+    #[test]
+    pub fn generic() {
+        round_trip(
+            Generic::Value("hi".to_string()),
+            r#"{"t":"value","c":"hi"}"#,
+        );
+        round_trip(Generic::<u8>::Marker(PhantomData), r#"{"t":"marker"}"#);
+        // Rejected variants are written, but never read back
+        assert_eq!(
+            serde_json::to_string(&Generic::Hidden(1)).unwrap(),
+            r#"{"t":"hidden","c":1}"#
+        );
+        assert!(serde_json::from_str::<Generic<u8>>(r#"{"t":"hidden","c":1}"#).is_err());
+    }
+
+    #[test]
+    pub fn tag_anywhere() {
+        // Like serde_derive, whatever comes before the tag is buffered
+        assert_eq!(
+            serde_json::from_str::<Internal>(
+                r#"{"level":4,"extra":[1,{"a":null}],"type":"set_light","on":true}"#
+            )
+            .unwrap(),
+            Internal::SetLight { level: 4, on: true }
+        );
+        assert_eq!(
+            serde_json::from_str::<Internal>(r#"{"extra":1,"type":"power_off"}"#).unwrap(),
+            Internal::PowerOff
+        );
+        assert_eq!(
+            serde_json::from_str::<Adjacent>(r#"{"c":{"on":true,"level":9},"t":"set_light"}"#)
+                .unwrap(),
+            Adjacent::SetLight { level: 9, on: true }
+        );
+        assert_eq!(
+            serde_json::from_str::<Adjacent>(r#"{"c":[1,-2],"x":0,"t":"move"}"#).unwrap(),
+            Adjacent::Move(1, -2)
+        );
+        assert_eq!(
+            serde_json::from_str::<Adjacent>(r#"{"c":null,"t":"power_off"}"#).unwrap(),
+            Adjacent::PowerOff
+        );
+        assert_eq!(
+            serde_json::from_str::<Generic<String>>(r#"{"c":"hi","t":"value"}"#).unwrap(),
+            Generic::Value("hi".to_string())
+        );
+
+        let late =
+            serde_json::from_str::<Internal>(r#"{"level":4,"type":"set_light"}"#).unwrap_err();
+        assert!(late.to_string().starts_with("missing field `on`"));
+        let duplicate =
+            serde_json::from_str::<Adjacent>(r#"{"c":7,"c":8,"t":"go_to"}"#).unwrap_err();
+        assert!(duplicate.to_string().starts_with("duplicate field `c`"));
+    }
+
+    #[test]
+    pub fn tag_after_wide_integers() {
+        let wide = |entries: Vec<(&'static str, Value)>| {
+            let entries = entries
+                .into_iter()
+                .map(|(key, value)| (Value::Str(key), value));
+            Wide::deserialize(value::MapDeserializer::new(entries))
+        };
+
+        assert_eq!(
+            wide(vec![
+                ("value", Value::I128(i128::MIN)),
+                ("type", Value::Str("signed")),
+            ])
+            .unwrap(),
+            Wide::Signed { value: i128::MIN }
+        );
+        assert_eq!(
+            wide(vec![
+                ("value", Value::U128(u128::MAX)),
+                ("type", Value::Str("unsigned")),
+            ])
+            .unwrap(),
+            Wide::Unsigned { value: u128::MAX }
+        );
+    }
+
+    #[test]
+    pub fn tag_spellings() {
+        // The tag is read the way `FromStr` reads the variant's name
+        for (input, expected) in [
+            ("halt", Some(Relaxed::Halt)),
+            ("HALT", Some(Relaxed::Halt)),
+            ("h", Some(Relaxed::Halt)),
+            ("resu", Some(Relaxed::Resume)),
+            ("res", None),
+            ("Resu", None),
+            ("reboot", None),
+        ] {
+            let json = format!(r#"{{"kind":"{}"}}"#, input);
+            let parsed = serde_json::from_str::<Relaxed>(&json).ok();
+            assert_eq!(parsed, expected, "{}", input);
+            assert_eq!(Relaxed::from_str(input).ok(), expected, "{}", input);
+        }
+
+        assert_eq!(
+            serde_json::from_str::<Relaxed>(r#"{"kind":"SetSpeed","speed":3}"#).unwrap(),
+            Relaxed::SetSpeed { speed: 3 }
+        );
+        let ambiguous = serde_json::from_str::<Relaxed>(r#"{"kind":"res"}"#).unwrap_err();
+        assert!(ambiguous
+            .to_string()
+            .starts_with("ambiguous variant `res`, could be any of: resume, restart"));
+
+        assert_eq!(
+            serde_json::from_str::<Numeric>(r#"{"t":"7"}"#).unwrap(),
+            Numeric::Seven
+        );
+        assert_eq!(Numeric::from_str("7").unwrap(), Numeric::Seven);
+    }
+
+    #[test]
+    pub fn errors() {
+        let unknown = serde_json::from_str::<Internal>(r#"{"type":"reboot"}"#).unwrap_err();
+        assert!(unknown
+            .to_string()
+            .starts_with("unknown variant `reboot`, expected `power_off` or `set_light`"));
+
+        let missing = serde_json::from_str::<Adjacent>(r#"{"t":"go_to"}"#).unwrap_err();
+        assert!(missing.to_string().starts_with("missing field `c`"));
+
+        let duplicate =
+            serde_json::from_str::<Internal>(r#"{"type":"set_light","on":true,"on":false}"#)
+                .unwrap_err();
+        assert!(duplicate.to_string().starts_with("duplicate field `on`"));
+    }
+}
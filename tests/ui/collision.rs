@@ -0,0 +1,16 @@
+use to_and_fro::ToAndFro;
+
+#[derive(ToAndFro)]
+#[to_and_fro(input_case = "lower")]
+pub enum TestEnum {
+    HelloWorld,
+    #[to_and_fro(rename = "helloworld")]
+    Greeting,
+    #[to_and_fro(alias("helloworld", "hi"))]
+    Salutation,
+    #[to_and_fro(reject)]
+    #[to_and_fro(rename = "hi")]
+    Ignored,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use to_and_fro::ToAndFro;
+
+#[derive(ToAndFro)]
+#[to_and_fro(input_case("snake", "lower"))]
+pub enum TestEnum {
+    HelloWorld,
+    #[to_and_fro(rename = "helloworld")]
+    Greeting,
+}
+
+#[derive(ToAndFro)]
+#[to_and_fro(output_case("snake", "kebab"))]
+pub enum TestEnum2 {
+    HelloWorld,
+}
+
+fn main() {}
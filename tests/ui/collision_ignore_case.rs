@@ -0,0 +1,15 @@
+use to_and_fro::ToAndFro;
+
+#[derive(ToAndFro)]
+pub enum TestEnum {
+    #[to_and_fro(parse(any_case))]
+    HelloWorld,
+    #[to_and_fro(rename = "hello-world")]
+    Greeting,
+    #[to_and_fro(parse(ignore_case))]
+    Salutation,
+    #[to_and_fro(rename = "SALUTATION")]
+    Shout,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use to_and_fro::ToAndFro;
+
+#[derive(ToAndFro)]
+#[to_and_fro(input_case = "snek")]
+#[to_and_fro(output_case = "not a case")]
+pub enum TestEnum {
+    #[to_and_fro(casing = "kebap")]
+    HelloWorld,
+    #[to_and_fro(reject = "please")]
+    FooBar,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use to_and_fro::ToAndFro;
+
+#[derive(ToAndFro)]
+#[to_and_fro(default = "Missing")]
+pub enum TestEnum {
+    HelloWorld,
+    FooBar,
+}
+
+#[derive(ToAndFro)]
+#[to_and_fro(default = "Payload")]
+pub enum TestEnum2 {
+    Unit,
+    Payload(u32),
+}
+
+fn main() {}
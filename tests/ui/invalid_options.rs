@@ -0,0 +1,76 @@
+use to_and_fro::ToAndFro;
+
+#[derive(ToAndFro)]
+#[to_and_fro(skip(Display))]
+pub enum TestEnum {
+    HelloWorld,
+}
+
+#[derive(ToAndFro)]
+#[to_and_fro(skip(Copy), impls(Clone))]
+pub enum TestEnum2 {
+    HelloWorld,
+}
+
+#[derive(ToAndFro)]
+#[to_and_fro(casng = "snake")]
+pub enum TestEnum3 {
+    HelloWorld,
+}
+
+#[derive(ToAndFro)]
+#[to_and_fro(parse(allow_numeric))]
+pub enum TestEnum4 {
+    HelloWorld(u8),
+}
+
+#[derive(ToAndFro)]
+#[to_and_fro(set, map)]
+pub enum TestEnum5 {
+    HelloWorld(u8),
+}
+
+#[derive(ToAndFro)]
+#[to_and_fro(list(duplicates = "merge"))]
+pub enum TestEnum6 {
+    HelloWorld,
+}
+
+#[derive(ToAndFro)]
+#[to_and_fro(alloc)]
+pub enum TestEnum7 {
+    HelloWorld,
+}
+
+#[derive(ToAndFro)]
+#[to_and_fro(serde = "index")]
+pub enum TestEnum8 {
+    HelloWorld(u8),
+}
+
+#[derive(ToAndFro)]
+#[to_and_fro(serde = "packed")]
+pub enum TestEnum9 {
+    HelloWorld,
+}
+
+#[derive(ToAndFro)]
+#[to_and_fro(serde(tag = "kind"))]
+pub enum TestEnum10 {
+    HelloWorld(u8),
+    Greeting { kind: u8 },
+}
+
+#[derive(ToAndFro)]
+#[to_and_fro(serde(content = "value"))]
+pub enum TestEnum11 {
+    HelloWorld,
+}
+
+#[derive(ToAndFro)]
+#[to_and_fro(serde = "index", serde(tag = "kind"))]
+pub enum TestEnum12 {
+    HelloWorld,
+}
+
+fn main() {}
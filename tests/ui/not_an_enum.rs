@@ -0,0 +1,8 @@
+use to_and_fro::ToAndFro;
+
+#[derive(ToAndFro)]
+pub struct TestStruct {
+    field: u32,
+}
+
+fn main() {}
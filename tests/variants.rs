@@ -0,0 +1,62 @@
+extern crate to_and_fro;
+
+#[cfg(test)]
+mod tests {
+
+    use to_and_fro::ToAndFro;
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(input_case("snake", "kebab"), output_case = "kebab")]
+    pub enum TestEnum {
+        HelloWorld,
+        #[to_and_fro(alias("hi"))]
+        Greeting,
+        #[to_and_fro(reject)]
+        Secret,
+    }
+
+    #[derive(ToAndFro)]
+    #[to_and_fro(casing = "snake")]
+    pub enum Shape {
+        Circle(u32),
+        Square { side: u32 },
+        Empty,
+    }
+
+    const FIRST: TestEnum = TestEnum::ALL[0];
+    const COUNT: usize = TestEnum::COUNT;
+
+    #[test]
+    pub fn all() {
+        assert_eq!(FIRST, TestEnum::HelloWorld);
+        assert_eq!(
+            TestEnum::ALL,
+            &[TestEnum::HelloWorld, TestEnum::Greeting, TestEnum::Secret]
+        );
+        assert_eq!(COUNT, 3);
+        assert_eq!(TestEnum::NAMES, &["hello-world", "greeting", "secret"]);
+        assert_eq!(
+            TestEnum::PARSE_NAMES,
+            &["hello_world", "hello-world", "greeting", "hi"]
+        );
+    }
+
+    #[test]
+    pub fn iter() {
+        let mut iter = TestEnum::iter();
+        assert_eq!(iter.len(), TestEnum::COUNT);
+        assert_eq!(iter.next_back(), Some(TestEnum::Secret));
+        assert_eq!(iter.next(), Some(TestEnum::HelloWorld));
+        assert_eq!(iter.len(), 1);
+
+        let names = TestEnum::iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        assert_eq!(names, TestEnum::NAMES);
+    }
+
+    #[test]
+    pub fn with_fields() {
+        assert_eq!(Shape::COUNT, 3);
+        assert_eq!(Shape::NAMES, &["circle", "square", "empty"]);
+        assert_eq!(Shape::PARSE_NAMES, &["circle", "square", "empty"]);
+    }
+}